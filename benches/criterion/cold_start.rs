@@ -0,0 +1,55 @@
+use crate::isolate::vms_under_test;
+use crate::memory::bench_function;
+use benchmark_utils::{
+    CallTyped as _, FuncType, InputEncoding, StartupTestId, Val, ValType, read_benchmark_file,
+};
+use criterion::{Criterion, criterion_group};
+use std::time::Duration;
+
+criterion_group!(
+    name = bench_cold_start;
+    config = Criterion::default()
+        .sample_size(10)
+        .measurement_time(Duration::from_millis(2000))
+        .warm_up_time(Duration::from_millis(1000));
+    targets =
+        bench_trivial,
+);
+
+/// The `env.host` function imported by the trivial module: returns its parameter.
+fn host(params: &[Val], results: &mut [Val]) {
+    results[0] = params[0];
+}
+
+/// Benchmarks a cold start on the trivial module as paid by serverless-style embedders.
+///
+/// In contrast to the `startup` benchmarks, every iteration also sets up a fresh runtime,
+/// e.g. creating its engine or isolate, links the module's import and calls its `run` export
+/// once. For lazy compilation modes this includes translating `run` on its first call.
+fn bench_trivial(c: &mut Criterion) {
+    let id = StartupTestId::Trivial;
+    let wasm = read_benchmark_file(InputEncoding::Wat, id.into());
+    let group = format!("cold-start/{id}");
+    let mut g = c.benchmark_group(&group);
+    for vm in vms_under_test(&group) {
+        if vm.setup(id.into()).is_none() {
+            continue;
+        }
+        let bench_id = vm.id().to_string();
+        let full_id = format!("cold-start/{id}/{bench_id}");
+        bench_function(&mut g, &bench_id, &full_id, |b| {
+            b.iter(|| {
+                let mut rt = vm.setup(id.into()).expect("checked above");
+                rt.link_func(
+                    "env",
+                    "host",
+                    FuncType::new([ValType::I32], [ValType::I32]),
+                    host,
+                );
+                let mut instance = rt.instantiate(&wasm[..]);
+                let result = instance.call_typed::<i32, i32>("run", 41).unwrap();
+                assert_eq!(result, 42);
+            });
+        });
+    }
+}
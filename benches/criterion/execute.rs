@@ -1,11 +1,15 @@
-use benchmark_utils::{CallTyped as _, ExecuteTestId};
-use benchmark_utils::{InputEncoding, Val, read_benchmark_file, wat2wasm};
+use crate::isolate::vms_under_test;
+use crate::memory::bench_function;
+use crate::perf;
+use benchmark_utils::{
+    CallTyped as _, ExecuteTestId, FuncType, HostFunc, ModuleInstance, TypedFunc,
+};
+use benchmark_utils::{InputEncoding, Val, ValType, read_benchmark_file, wat2wasm};
 use core::fmt;
 use core::slice;
-use criterion::{Criterion, criterion_group};
+use criterion::{Criterion, Throughput, criterion_group};
 use std::fs;
 use std::time::Duration;
-use wasmi_benchmarks::vms_under_test;
 
 criterion_group!(
     name = bench_execute;
@@ -35,14 +39,65 @@ criterion_group!(
         bench_reverse_complement,
         bench_regex_redux,
         bench_bulk_ops,
+        bench_host_call_nullary,
+        bench_host_call_i64x4,
+        bench_host_call_floats,
+        bench_host_call_multi_value,
+        bench_host_call_export,
+        bench_host_call_export_typed,
+        bench_host_call_export_resolved,
 );
 
+// The inputs of the test cases, shared with the `first-call` benchmarks of the same test cases.
+
+/// The number of loop iterations of `counter-local` and `counter-param`.
+pub const COUNTER_N: i32 = 1_000_000;
+/// The number of loop iterations of `counter-global`.
+pub const COUNTER_GLOBAL_N: i32 = 500_000;
+/// The Fibonacci number computed by `fibonacci-rec`.
+pub const FIBONACCI_REC_N: i64 = 30;
+/// The Fibonacci number computed by `fibonacci-iter`.
+pub const FIBONACCI_ITER_N: i64 = 2_000_000;
+/// The Fibonacci number computed by `fibonacci-tail`.
+pub const FIBONACCI_TAIL_N: i64 = 1_000_000;
+/// The `run` parameter of `bulk-ops`.
+pub const BULK_OPS_N: i64 = 5_000;
+/// The number of elements sorted by `sort`.
+pub const SORT_LEN: i32 = 1_000_000;
+/// The number of elements sorted by `sort-dyn`.
+pub const SORT_DYN_LEN: i32 = 400_000;
+/// The limit of the primes sieved by `prime-sieve`.
+pub const PRIME_SIEVE_LEN: i64 = 10_000_000;
+/// The `setup` parameter of `matrix-multiply`.
+pub const MATRIX_MULTIPLY_N: i32 = 400;
+/// The `setup` parameter of `nbody`.
+pub const NBODY_N: i32 = 400;
+/// The `setup` parameter of `argon2`.
+pub const ARGON2_LEN: i32 = 3_000;
+/// The `setup` parameter of `mandelbrot`.
+pub const MANDELBROT_N: i32 = 150;
+/// The `setup` parameter of `spectralnorm`.
+pub const SPECTRALNORM_N: i32 = 500;
+/// The text compressed by `compression` and counted by `word-count`.
+pub const ALICE_TXT: &str = "res/rust/res/alice29.txt";
+/// The JSON parsed by `json-parse`.
+pub const CITM_CATALOG_JSON: &str = "res/rust/res/citm_catalog.json";
+/// The DNA processed by `reverse-complement` and `regex-redux`.
+pub const DNA_TXT: &str = "res/rust/cases/reverse-complement/input.txt";
+
 /// Generic utility benchmark function for Wasm functions of type: T -> T
-fn execute_benchmark<T>(c: &mut Criterion, id: ExecuteTestId, input: T, encoding: InputEncoding)
-where
+///
+/// The `throughput` is the work done by a single call, if meaningful for the test case.
+fn execute_benchmark<T>(
+    c: &mut Criterion,
+    id: ExecuteTestId,
+    input: T,
+    encoding: InputEncoding,
+    throughput: Option<Throughput>,
+) where
     T: Into<Val> + Copy + fmt::Display,
 {
-    execute_benchmark_with_val(c, id, input.into(), encoding)
+    execute_benchmark_with_val(c, id, input.into(), encoding, throughput)
 }
 
 /// Non-generic utility benchmark function for Wasm functions of type: T -> T
@@ -51,21 +106,27 @@ fn execute_benchmark_with_val(
     id: ExecuteTestId,
     input: Val,
     encoding: InputEncoding,
+    throughput: Option<Throughput>,
 ) {
-    let wasm = read_benchmark_file(encoding, id.into());
-    let mut g = c.benchmark_group(format!("execute/{id}"));
-    for vm in vms_under_test() {
+    // Converted once up front, so that neither the timed runs nor the memory pass include it.
+    let wasm = wat2wasm(&read_benchmark_file(encoding, id.into())[..]);
+    let group = format!("execute/{id}");
+    let mut g = c.benchmark_group(&group);
+    if let Some(throughput) = throughput {
+        g.throughput(throughput);
+    }
+    for vm in vms_under_test(&group) {
         let Some(rt) = vm.setup(id.into()) else {
             continue;
         };
         let bench_id = format!("{}/{}", vm.id(), input);
-        g.bench_function(&bench_id, |b| {
-            // `load` consumes the runtime instance, so it runs once here rather than inside
-            // the `bench_function` closure (which is `FnMut` and only times `call` via `b.iter`).
-            let wasm = wat2wasm(&wasm[..]);
+        let full_id = format!("execute/{id}/{bench_id}");
+        bench_function(&mut g, &bench_id, &full_id, |b| {
+            // Instantiates once per `bench_function` closure call, outside of the timed routine of
+            // `perf::iter`, so that only `call` is timed.
             let mut instance = rt.instantiate(&wasm[..]);
             let mut result = Val::default_for_ty(input.ty());
-            b.iter(|| {
+            perf::iter(b, &full_id, || {
                 instance
                     .call("run", slice::from_ref(&input), slice::from_mut(&mut result))
                     .unwrap();
@@ -74,38 +135,41 @@ fn execute_benchmark_with_val(
     }
 }
 
+/// Benchmarks a counter test case that loops `n` times, reporting loop iterations per second.
+fn execute_counter_benchmark(c: &mut Criterion, id: ExecuteTestId, n: i32) {
+    let throughput = Throughput::Elements(n as u64);
+    execute_benchmark::<i32>(c, id, n, InputEncoding::Wat, Some(throughput))
+}
+
 fn bench_counter_local(c: &mut Criterion) {
-    execute_benchmark::<i32>(
-        c,
-        ExecuteTestId::CounterLocal,
-        1_000_000,
-        InputEncoding::Wat,
-    )
+    execute_counter_benchmark(c, ExecuteTestId::CounterLocal, COUNTER_N)
 }
 
 fn bench_counter_param(c: &mut Criterion) {
-    execute_benchmark::<i32>(
-        c,
-        ExecuteTestId::CounterParam,
-        1_000_000,
-        InputEncoding::Wat,
-    )
+    execute_counter_benchmark(c, ExecuteTestId::CounterParam, COUNTER_N)
 }
 
 fn bench_counter_global(c: &mut Criterion) {
-    execute_benchmark::<i32>(c, ExecuteTestId::CounterGlobal, 500_000, InputEncoding::Wat)
+    execute_counter_benchmark(c, ExecuteTestId::CounterGlobal, COUNTER_GLOBAL_N)
 }
 
 fn bench_fibonacci_rec(c: &mut Criterion) {
-    execute_benchmark::<i64>(c, ExecuteTestId::FibonacciRec, 30, InputEncoding::Wat)
+    execute_benchmark::<i64>(
+        c,
+        ExecuteTestId::FibonacciRec,
+        FIBONACCI_REC_N,
+        InputEncoding::Wat,
+        None,
+    )
 }
 
 fn bench_fibonacci_iter(c: &mut Criterion) {
     execute_benchmark::<i64>(
         c,
         ExecuteTestId::FibonacciIter,
-        2_000_000,
+        FIBONACCI_ITER_N,
         InputEncoding::Wat,
+        None,
     )
 }
 
@@ -113,29 +177,176 @@ fn bench_fibonacci_tail(c: &mut Criterion) {
     execute_benchmark::<i64>(
         c,
         ExecuteTestId::FibonacciTail,
-        1_000_000,
+        FIBONACCI_TAIL_N,
         InputEncoding::Wat,
+        None,
     )
 }
 
 fn bench_bulk_ops(c: &mut Criterion) {
-    execute_benchmark::<i64>(c, ExecuteTestId::BulkOps, 5_000, InputEncoding::Wat)
+    execute_benchmark::<i64>(
+        c,
+        ExecuteTestId::BulkOps,
+        BULK_OPS_N,
+        InputEncoding::Wat,
+        None,
+    )
+}
+
+/// Benchmarks a host call test case whose `run(n)` calls its `env.host` import `n` times,
+/// reporting host calls per second.
+///
+/// Asserts that `run(n)` returns `expected(n)`, so that a broken host function fails loudly.
+fn execute_host_call_benchmark(
+    c: &mut Criterion,
+    id: ExecuteTestId,
+    ty: FuncType,
+    host: HostFunc,
+    expected: fn(i32) -> i32,
+) {
+    let n: i32 = 100_000;
+    let wasm = read_benchmark_file(InputEncoding::Wat, id.into());
+    let group = format!("execute/{id}");
+    let mut g = c.benchmark_group(&group);
+    g.throughput(Throughput::Elements(n as u64));
+    for vm in vms_under_test(&group) {
+        let Some(mut rt) = vm.setup(id.into()) else {
+            continue;
+        };
+        rt.link_func("env", "host", ty.clone(), host);
+        let bench_id = format!("{}/{}", vm.id(), n);
+        let full_id = format!("execute/{id}/{bench_id}");
+        bench_function(&mut g, &bench_id, &full_id, |b| {
+            let mut instance = rt.instantiate(&wasm[..]);
+            perf::iter(b, &full_id, || {
+                let result = instance.call_typed::<i32, i32>("run", n).unwrap();
+                assert_eq!(result, expected(n));
+            });
+        });
+    }
+}
+
+fn bench_host_call_nullary(c: &mut Criterion) {
+    fn host(_params: &[Val], _results: &mut [Val]) {}
+    let ty = FuncType::new([], []);
+    // `run` returns its loop counter, which counts down to zero.
+    execute_host_call_benchmark(c, ExecuteTestId::HostCallNullary, ty, host, |_n| 0)
+}
+
+fn bench_host_call_i64x4(c: &mut Criterion) {
+    fn host(params: &[Val], results: &mut [Val]) {
+        let sum = params
+            .iter()
+            .map(|param| param.unwrap_i64())
+            .fold(0, i64::wrapping_add);
+        results[0] = Val::I64(sum);
+    }
+    let ty = FuncType::new([ValType::I64; 4], [ValType::I64]);
+    // Every call adds `1 + 2 + 3` to the accumulator.
+    execute_host_call_benchmark(c, ExecuteTestId::HostCallI64x4, ty, host, |n| 6 * n)
+}
+
+fn bench_host_call_floats(c: &mut Criterion) {
+    fn host(params: &[Val], results: &mut [Val]) {
+        let [a, b, c, d] = params else {
+            panic!("expected 4 parameters but found {}", params.len())
+        };
+        let sum =
+            f64::from(a.unwrap_f32()) + b.unwrap_f64() + f64::from(c.unwrap_f32()) + d.unwrap_f64();
+        results[0] = Val::F64(sum);
+    }
+    let ty = FuncType::new(
+        [ValType::F32, ValType::F64, ValType::F32, ValType::F64],
+        [ValType::F64],
+    );
+    // Every call adds `0.5 + 0.25 + 0.25` to the accumulator.
+    execute_host_call_benchmark(c, ExecuteTestId::HostCallFloats, ty, host, |n| n)
+}
+
+fn bench_host_call_multi_value(c: &mut Criterion) {
+    fn host(params: &[Val], results: &mut [Val]) {
+        results[0] = Val::I64(params[1].unwrap_i64().wrapping_add(1));
+        results[1] = Val::I32(params[0].unwrap_i32().wrapping_add(1));
+    }
+    let ty = FuncType::new([ValType::I32, ValType::I64], [ValType::I64, ValType::I32]);
+    // Every call increments both values, of which `run` returns the `i32`.
+    execute_host_call_benchmark(c, ExecuteTestId::HostCallMultiValue, ty, host, |n| n)
+}
+
+/// Calls the `run` export of `host-call-export` with a parameter and returns its result.
+///
+/// Receives `run` resolved once up front for the calls that make use of it.
+type ExportCall = fn(&mut Box<dyn ModuleInstance>, &TypedFunc<i32, i32>, i32) -> i32;
+
+/// Benchmarks a host loop calling the tiny `run` export of `host-call-export` `n` times via `call`,
+/// reporting calls per second.
+fn execute_export_call_benchmark(c: &mut Criterion, id: ExecuteTestId, call: ExportCall) {
+    let n: i32 = 100_000;
+    let wasm = read_benchmark_file(InputEncoding::Wat, ExecuteTestId::HostCallExport.into());
+    let group = format!("execute/{id}");
+    let mut g = c.benchmark_group(&group);
+    g.throughput(Throughput::Elements(n as u64));
+    for vm in vms_under_test(&group) {
+        let Some(rt) = vm.setup(id.into()) else {
+            continue;
+        };
+        let bench_id = format!("{}/{}", vm.id(), n);
+        let full_id = format!("execute/{id}/{bench_id}");
+        bench_function(&mut g, &bench_id, &full_id, |b| {
+            let mut instance = rt.instantiate(&wasm[..]);
+            let run = instance.typed_func::<i32, i32>("run").unwrap();
+            perf::iter(b, &full_id, || {
+                // Every call adds one to the result of the previous one.
+                let result = (0..n).fold(0, |acc, _| call(&mut instance, &run, acc));
+                assert_eq!(result, n);
+            });
+        });
+    }
+}
+
+fn bench_host_call_export(c: &mut Criterion) {
+    execute_export_call_benchmark(c, ExecuteTestId::HostCallExport, |instance, _run, n| {
+        let mut result = Val::I32(0);
+        instance
+            .call("run", &[Val::I32(n)], slice::from_mut(&mut result))
+            .unwrap();
+        result.unwrap_i32()
+    })
+}
+
+fn bench_host_call_export_typed(c: &mut Criterion) {
+    execute_export_call_benchmark(
+        c,
+        ExecuteTestId::HostCallExportTyped,
+        |instance, _run, n| instance.call_typed::<i32, i32>("run", n).unwrap(),
+    )
+}
+
+fn bench_host_call_export_resolved(c: &mut Criterion) {
+    execute_export_call_benchmark(
+        c,
+        ExecuteTestId::HostCallExportResolved,
+        |instance, run, n| run.call(&mut **instance, n).unwrap(),
+    )
 }
 
 fn bench_sort(c: &mut Criterion) {
     let id = ExecuteTestId::Sort;
     let wasm = read_benchmark_file(InputEncoding::RustCompiledWasm, id.into());
-    let mut g = c.benchmark_group(format!("execute/{id}"));
-    for vm in vms_under_test() {
+    let group = format!("execute/{id}");
+    let mut g = c.benchmark_group(&group);
+    let len = SORT_LEN;
+    g.throughput(Throughput::Elements(len as u64));
+    for vm in vms_under_test(&group) {
         let Some(rt) = vm.setup(id.into()) else {
             continue;
         };
-        let len: i32 = 1_000_000;
         let bench_id = format!("{}/{}", vm.id(), len);
-        g.bench_function(&bench_id, |b| {
+        let full_id = format!("execute/{id}/{bench_id}");
+        bench_function(&mut g, &bench_id, &full_id, |b| {
             let mut instance = rt.instantiate(&wasm[..]);
             let data = instance.call_typed::<i32, i32>("setup", len).unwrap();
-            b.iter(|| {
+            perf::iter(b, &full_id, || {
                 instance.call_typed::<i32, ()>("run", data).unwrap();
             });
             instance.call_typed::<i32, ()>("teardown", data).unwrap();
@@ -146,17 +357,20 @@ fn bench_sort(c: &mut Criterion) {
 fn bench_sort_dyn(c: &mut Criterion) {
     let id = ExecuteTestId::SortDyn;
     let wasm = read_benchmark_file(InputEncoding::RustCompiledWasm, id.into());
-    let mut g = c.benchmark_group(format!("execute/{id}"));
-    for vm in vms_under_test() {
+    let group = format!("execute/{id}");
+    let mut g = c.benchmark_group(&group);
+    let len = SORT_DYN_LEN;
+    g.throughput(Throughput::Elements(len as u64));
+    for vm in vms_under_test(&group) {
         let Some(rt) = vm.setup(id.into()) else {
             continue;
         };
-        let len: i32 = 400_000;
         let bench_id = format!("{}/{}", vm.id(), len);
-        g.bench_function(&bench_id, |b| {
+        let full_id = format!("execute/{id}/{bench_id}");
+        bench_function(&mut g, &bench_id, &full_id, |b| {
             let mut instance = rt.instantiate(&wasm[..]);
             let data = instance.call_typed::<i32, i32>("setup", len).unwrap();
-            b.iter(|| {
+            perf::iter(b, &full_id, || {
                 instance.call_typed::<i32, ()>("run", data).unwrap();
             });
             instance.call_typed::<i32, ()>("teardown", data).unwrap();
@@ -167,17 +381,20 @@ fn bench_sort_dyn(c: &mut Criterion) {
 fn bench_execute_prime_sieve(c: &mut Criterion) {
     let id = ExecuteTestId::PrimeSieve;
     let wasm = read_benchmark_file(InputEncoding::RustCompiledWasm, id.into());
-    let mut g = c.benchmark_group(format!("execute/{id}"));
-    for vm in vms_under_test() {
+    let group = format!("execute/{id}");
+    let mut g = c.benchmark_group(&group);
+    let len = PRIME_SIEVE_LEN;
+    g.throughput(Throughput::Elements(len as u64));
+    for vm in vms_under_test(&group) {
         let Some(rt) = vm.setup(id.into()) else {
             continue;
         };
-        let len: i64 = 10_000_000;
         let bench_id = format!("{}/{}", vm.id(), len);
-        g.bench_function(&bench_id, |b| {
+        let full_id = format!("execute/{id}/{bench_id}");
+        bench_function(&mut g, &bench_id, &full_id, |b| {
             let mut instance = rt.instantiate(&wasm[..]);
             let data = instance.call_typed::<i64, i32>("setup", len).unwrap();
-            b.iter(|| {
+            perf::iter(b, &full_id, || {
                 instance.call_typed::<i32, ()>("run", data).unwrap();
             });
             let len_primes = instance.call_typed::<i32, i64>("len_primes", data).unwrap();
@@ -194,17 +411,19 @@ fn bench_execute_prime_sieve(c: &mut Criterion) {
 fn bench_matrix_multiply(c: &mut Criterion) {
     let id = ExecuteTestId::MatrixMultiply;
     let wasm = read_benchmark_file(InputEncoding::RustCompiledWasm, id.into());
-    let mut g = c.benchmark_group(format!("execute/{id}"));
-    for vm in vms_under_test() {
+    let group = format!("execute/{id}");
+    let mut g = c.benchmark_group(&group);
+    for vm in vms_under_test(&group) {
         let Some(rt) = vm.setup(id.into()) else {
             continue;
         };
-        let n: i32 = 400;
+        let n = MATRIX_MULTIPLY_N;
         let bench_id = format!("{}/{}", vm.id(), n);
-        g.bench_function(&bench_id, |b| {
+        let full_id = format!("execute/{id}/{bench_id}");
+        bench_function(&mut g, &bench_id, &full_id, |b| {
             let mut instance = rt.instantiate(&wasm[..]);
             let data = instance.call_typed::<i32, i32>("setup", n).unwrap();
-            b.iter(|| {
+            perf::iter(b, &full_id, || {
                 instance.call_typed::<i32, ()>("run", data).unwrap();
             });
             instance.call_typed::<i32, ()>("teardown", data).unwrap();
@@ -215,17 +434,19 @@ fn bench_matrix_multiply(c: &mut Criterion) {
 fn bench_nbody(c: &mut Criterion) {
     let id = ExecuteTestId::Nbody;
     let wasm = read_benchmark_file(InputEncoding::RustCompiledWasm, id.into());
-    let mut g = c.benchmark_group(format!("execute/{id}"));
-    for vm in vms_under_test() {
+    let group = format!("execute/{id}");
+    let mut g = c.benchmark_group(&group);
+    for vm in vms_under_test(&group) {
         let Some(rt) = vm.setup(id.into()) else {
             continue;
         };
-        let n: i32 = 400;
+        let n = NBODY_N;
         let bench_id = format!("{}/{}", vm.id(), n);
-        g.bench_function(&bench_id, |b| {
+        let full_id = format!("execute/{id}/{bench_id}");
+        bench_function(&mut g, &bench_id, &full_id, |b| {
             let mut instance = rt.instantiate(&wasm[..]);
             let data = instance.call_typed::<i32, i32>("setup", n).unwrap();
-            b.iter(|| {
+            perf::iter(b, &full_id, || {
                 instance.call_typed::<i32, ()>("run", data).unwrap();
             });
             instance.call_typed::<i32, ()>("teardown", data).unwrap();
@@ -236,17 +457,19 @@ fn bench_nbody(c: &mut Criterion) {
 fn bench_argon2(c: &mut Criterion) {
     let id = ExecuteTestId::Argon2;
     let wasm = read_benchmark_file(InputEncoding::RustCompiledWasm, id.into());
-    let mut g = c.benchmark_group(format!("execute/{id}"));
-    for vm in vms_under_test() {
+    let group = format!("execute/{id}");
+    let mut g = c.benchmark_group(&group);
+    for vm in vms_under_test(&group) {
         let Some(rt) = vm.setup(id.into()) else {
             continue;
         };
-        let len: i32 = 3_000;
+        let len = ARGON2_LEN;
         let bench_id = format!("{}/{}", vm.id(), len);
-        g.bench_function(&bench_id, |b| {
+        let full_id = format!("execute/{id}/{bench_id}");
+        bench_function(&mut g, &bench_id, &full_id, |b| {
             let mut instance = rt.instantiate(&wasm[..]);
             let data = instance.call_typed::<i32, i32>("setup", len).unwrap();
-            b.iter(|| {
+            perf::iter(b, &full_id, || {
                 instance.call_typed::<i32, ()>("run", data).unwrap();
             });
             let output = instance.call_typed::<i32, i64>("output", data).unwrap();
@@ -259,16 +482,18 @@ fn bench_argon2(c: &mut Criterion) {
 fn bench_tiny_keccak(c: &mut Criterion) {
     let id = ExecuteTestId::TinyKeccak;
     let wasm = read_benchmark_file(InputEncoding::RustCompiledWasm, id.into());
-    let mut g = c.benchmark_group(format!("execute/{id}"));
-    for vm in vms_under_test() {
+    let group = format!("execute/{id}");
+    let mut g = c.benchmark_group(&group);
+    for vm in vms_under_test(&group) {
         let Some(rt) = vm.setup(id.into()) else {
             continue;
         };
         let bench_id = vm.id().to_string();
-        g.bench_function(&bench_id, |b| {
+        let full_id = format!("execute/{id}/{bench_id}");
+        bench_function(&mut g, &bench_id, &full_id, |b| {
             let mut instance = rt.instantiate(&wasm[..]);
             let data = instance.call_typed::<(), i32>("setup", ()).unwrap();
-            b.iter(|| {
+            perf::iter(b, &full_id, || {
                 instance.call_typed::<i32, ()>("run", data).unwrap();
             });
             instance.call_typed::<i32, ()>("teardown", data).unwrap();
@@ -279,17 +504,19 @@ fn bench_tiny_keccak(c: &mut Criterion) {
 fn bench_mandelbrot(c: &mut Criterion) {
     let id = ExecuteTestId::Mandelbrot;
     let wasm = read_benchmark_file(InputEncoding::RustCompiledWasm, id.into());
-    let mut g = c.benchmark_group(format!("execute/{id}"));
-    for vm in vms_under_test() {
+    let group = format!("execute/{id}");
+    let mut g = c.benchmark_group(&group);
+    for vm in vms_under_test(&group) {
         let Some(rt) = vm.setup(id.into()) else {
             continue;
         };
-        let n: i32 = 150;
+        let n = MANDELBROT_N;
         let bench_id = format!("{}/{}", vm.id(), n);
-        g.bench_function(&bench_id, |b| {
+        let full_id = format!("execute/{id}/{bench_id}");
+        bench_function(&mut g, &bench_id, &full_id, |b| {
             let mut instance = rt.instantiate(&wasm[..]);
             let data = instance.call_typed::<i32, i32>("setup", n).unwrap();
-            b.iter(|| {
+            perf::iter(b, &full_id, || {
                 instance.call_typed::<i32, ()>("run", data).unwrap();
             });
             let output = instance.call_typed::<i32, i64>("output", data).unwrap();
@@ -302,17 +529,19 @@ fn bench_mandelbrot(c: &mut Criterion) {
 fn bench_spectralnorm(c: &mut Criterion) {
     let id = ExecuteTestId::Spectralnorm;
     let wasm = read_benchmark_file(InputEncoding::RustCompiledWasm, id.into());
-    let mut g = c.benchmark_group(format!("execute/{id}"));
-    for vm in vms_under_test() {
+    let group = format!("execute/{id}");
+    let mut g = c.benchmark_group(&group);
+    for vm in vms_under_test(&group) {
         let Some(rt) = vm.setup(id.into()) else {
             continue;
         };
-        let n: i32 = 500;
+        let n = SPECTRALNORM_N;
         let bench_id = format!("{}/{}", vm.id(), n);
-        g.bench_function(&bench_id, |b| {
+        let full_id = format!("execute/{id}/{bench_id}");
+        bench_function(&mut g, &bench_id, &full_id, |b| {
             let mut instance = rt.instantiate(&wasm[..]);
             let data = instance.call_typed::<i32, i32>("setup", n).unwrap();
-            b.iter(|| {
+            perf::iter(b, &full_id, || {
                 instance.call_typed::<i32, ()>("run", data).unwrap();
             });
             let output = instance.call_typed::<i32, f64>("output", data).unwrap();
@@ -325,22 +554,25 @@ fn bench_spectralnorm(c: &mut Criterion) {
 fn bench_compression(c: &mut Criterion) {
     let id = ExecuteTestId::Compression;
     let wasm = read_benchmark_file(InputEncoding::RustCompiledWasm, id.into());
-    let mut g = c.benchmark_group(format!("execute/{id}"));
-    let uncompressed_input = fs::read_to_string("res/rust/res/alice29.txt").unwrap();
-    for vm in vms_under_test() {
+    let group = format!("execute/{id}");
+    let mut g = c.benchmark_group(&group);
+    let uncompressed_input = fs::read_to_string(ALICE_TXT).unwrap();
+    g.throughput(Throughput::BytesDecimal(uncompressed_input.len() as u64));
+    for vm in vms_under_test(&group) {
         let Some(rt) = vm.setup(id.into()) else {
             continue;
         };
         let len_input = uncompressed_input.len() as i32;
         let bench_id = format!("{}/{}", vm.id(), len_input);
-        g.bench_function(&bench_id, |b| {
+        let full_id = format!("execute/{id}/{bench_id}");
+        bench_function(&mut g, &bench_id, &full_id, |b| {
             let mut instance = rt.instantiate(&wasm[..]);
             let data = instance.call_typed::<i32, i32>("setup", len_input).unwrap();
             let input_ptr = instance.call_typed::<i32, i32>("input_ptr", data).unwrap();
             instance
                 .write_memory("memory", input_ptr as u32, uncompressed_input.as_bytes())
                 .unwrap();
-            b.iter(|| {
+            perf::iter(b, &full_id, || {
                 instance.call_typed::<i32, ()>("run", data).unwrap();
             });
             let len_compressed = instance
@@ -355,22 +587,25 @@ fn bench_compression(c: &mut Criterion) {
 fn bench_word_count(c: &mut Criterion) {
     let id = ExecuteTestId::WordCount;
     let wasm = read_benchmark_file(InputEncoding::RustCompiledWasm, id.into());
-    let mut g = c.benchmark_group(format!("execute/{id}"));
-    let uncompressed_input = fs::read_to_string("res/rust/res/alice29.txt").unwrap();
-    for vm in vms_under_test() {
+    let group = format!("execute/{id}");
+    let mut g = c.benchmark_group(&group);
+    let uncompressed_input = fs::read_to_string(ALICE_TXT).unwrap();
+    g.throughput(Throughput::BytesDecimal(uncompressed_input.len() as u64));
+    for vm in vms_under_test(&group) {
         let Some(rt) = vm.setup(id.into()) else {
             continue;
         };
         let len_input = uncompressed_input.len() as i32;
         let bench_id = format!("{}/{}", vm.id(), len_input);
-        g.bench_function(&bench_id, |b| {
+        let full_id = format!("execute/{id}/{bench_id}");
+        bench_function(&mut g, &bench_id, &full_id, |b| {
             let mut instance = rt.instantiate(&wasm[..]);
             let data = instance.call_typed::<i32, i32>("setup", len_input).unwrap();
             let input_ptr = instance.call_typed::<i32, i32>("input_ptr", data).unwrap();
             instance
                 .write_memory("memory", input_ptr as u32, uncompressed_input.as_bytes())
                 .unwrap();
-            b.iter(|| {
+            perf::iter(b, &full_id, || {
                 instance.call_typed::<i32, ()>("run", data).unwrap();
             });
             let len_unique_words = instance
@@ -389,22 +624,25 @@ fn bench_word_count(c: &mut Criterion) {
 fn bench_json_parse(c: &mut Criterion) {
     let id = ExecuteTestId::JsonParse;
     let wasm = read_benchmark_file(InputEncoding::RustCompiledWasm, id.into());
-    let mut g = c.benchmark_group(format!("execute/{id}"));
-    let uncompressed_input = fs::read_to_string("res/rust/res/citm_catalog.json").unwrap();
-    for vm in vms_under_test() {
+    let group = format!("execute/{id}");
+    let mut g = c.benchmark_group(&group);
+    let uncompressed_input = fs::read_to_string(CITM_CATALOG_JSON).unwrap();
+    g.throughput(Throughput::BytesDecimal(uncompressed_input.len() as u64));
+    for vm in vms_under_test(&group) {
         let Some(rt) = vm.setup(id.into()) else {
             continue;
         };
         let len_input = uncompressed_input.len() as i32;
         let bench_id = format!("{}/{}", vm.id(), len_input);
-        g.bench_function(&bench_id, |b| {
+        let full_id = format!("execute/{id}/{bench_id}");
+        bench_function(&mut g, &bench_id, &full_id, |b| {
             let mut instance = rt.instantiate(&wasm[..]);
             let data = instance.call_typed::<i32, i32>("setup", len_input).unwrap();
             let input_ptr = instance.call_typed::<i32, i32>("input_ptr", data).unwrap();
             instance
                 .write_memory("memory", input_ptr as u32, uncompressed_input.as_bytes())
                 .unwrap();
-            b.iter(|| {
+            perf::iter(b, &full_id, || {
                 instance.call_typed::<i32, ()>("run", data).unwrap();
             });
             let node_count = instance.call_typed::<i32, i64>("node_count", data).unwrap();
@@ -417,24 +655,27 @@ fn bench_json_parse(c: &mut Criterion) {
 fn bench_reverse_complement(c: &mut Criterion) {
     let id = ExecuteTestId::ReverseComplement;
     let wasm = read_benchmark_file(InputEncoding::RustCompiledWasm, id.into());
-    let mut g = c.benchmark_group(format!("execute/{id}"));
-    let input_text = fs::read_to_string("res/rust/cases/reverse-complement/input.txt").unwrap();
+    let group = format!("execute/{id}");
+    let mut g = c.benchmark_group(&group);
+    let input_text = fs::read_to_string(DNA_TXT).unwrap();
     let output_text = fs::read_to_string("res/rust/cases/reverse-complement/output.txt").unwrap();
-    let mut output_buffer = vec![0x0_u8; output_text.as_bytes().len()];
-    for vm in vms_under_test() {
+    let mut output_buffer = vec![0x0_u8; output_text.len()];
+    g.throughput(Throughput::BytesDecimal(input_text.len() as u64));
+    for vm in vms_under_test(&group) {
         let Some(rt) = vm.setup(id.into()) else {
             continue;
         };
         let len_input = input_text.len() as i32;
         let bench_id = format!("{}/{}", vm.id(), len_input);
-        g.bench_function(&bench_id, |b| {
+        let full_id = format!("execute/{id}/{bench_id}");
+        bench_function(&mut g, &bench_id, &full_id, |b| {
             let mut instance = rt.instantiate(&wasm[..]);
             let data = instance.call_typed::<i32, i32>("setup", len_input).unwrap();
             let input_ptr = instance.call_typed::<i32, i32>("input_ptr", data).unwrap();
             instance
                 .write_memory("memory", input_ptr as u32, input_text.as_bytes())
                 .unwrap();
-            b.iter(|| {
+            perf::iter(b, &full_id, || {
                 instance.call_typed::<i32, ()>("run", data).unwrap();
             });
             let output_ptr = instance.call_typed::<i32, i32>("output_ptr", data).unwrap();
@@ -450,22 +691,25 @@ fn bench_reverse_complement(c: &mut Criterion) {
 fn bench_regex_redux(c: &mut Criterion) {
     let id = ExecuteTestId::RegexRedux;
     let wasm = read_benchmark_file(InputEncoding::RustCompiledWasm, id.into());
-    let mut g = c.benchmark_group(format!("execute/{id}"));
-    let input_text = fs::read_to_string("res/rust/cases/reverse-complement/input.txt").unwrap();
-    for vm in vms_under_test() {
+    let group = format!("execute/{id}");
+    let mut g = c.benchmark_group(&group);
+    let input_text = fs::read_to_string(DNA_TXT).unwrap();
+    g.throughput(Throughput::BytesDecimal(input_text.len() as u64));
+    for vm in vms_under_test(&group) {
         let Some(rt) = vm.setup(id.into()) else {
             continue;
         };
         let len_input = input_text.len() as i32;
         let bench_id = format!("{}/{}", vm.id(), len_input);
-        g.bench_function(&bench_id, |b| {
+        let full_id = format!("execute/{id}/{bench_id}");
+        bench_function(&mut g, &bench_id, &full_id, |b| {
             let mut instance = rt.instantiate(&wasm[..]);
             let data = instance.call_typed::<i32, i32>("setup", len_input).unwrap();
             let input_ptr = instance.call_typed::<i32, i32>("input_ptr", data).unwrap();
             instance
                 .write_memory("memory", input_ptr as u32, input_text.as_bytes())
                 .unwrap();
-            b.iter(|| {
+            perf::iter(b, &full_id, || {
                 instance.call_typed::<i32, ()>("run", data).unwrap();
             });
             let output = instance.call_typed::<i32, i32>("output", data).unwrap();
@@ -0,0 +1,108 @@
+//! Applies Criterion's command line benchmark filter outside of Criterion.
+//!
+//! Criterion only applies its filter to the benchmarks it runs itself. The memory pass and the
+//! parent of isolated runs decide on their own which benchmarks to run, so they parse the
+//! positional `FILTER` argument together with `--exact` and `--ignored` just like Criterion does.
+
+use regex::Regex;
+use std::env;
+use std::sync::OnceLock;
+
+/// The options of Criterion's command line that take a value, see `Criterion::configure_from_args`.
+const VALUE_OPTIONS: [&str; 19] = [
+    "-c",
+    "--color",
+    "-s",
+    "--save-baseline",
+    "-b",
+    "--baseline",
+    "--baseline-lenient",
+    "--format",
+    "--profile-time",
+    "--load-baseline",
+    "--sample-size",
+    "--warm-up-time",
+    "--measurement-time",
+    "--nresamples",
+    "--noise-threshold",
+    "--confidence-level",
+    "--significance-level",
+    "--plotting-backend",
+    "--output-format",
+];
+
+/// The benchmark filter given on the command line.
+enum Filter {
+    /// Runs all benchmarks.
+    All,
+    /// Runs the benchmarks whose ID matches the regular expression.
+    Regex(Regex),
+    /// Runs the benchmark with exactly this ID.
+    Exact(String),
+    /// Runs no benchmarks, as with `--ignored`.
+    None,
+}
+
+impl Filter {
+    /// Parses the filter from the command line arguments of the benchmark binary.
+    fn from_args() -> Self {
+        let mut args = env::args().skip(1);
+        let mut filter = None;
+        let mut exact = false;
+        let mut ignored = false;
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--exact" => exact = true,
+                "--ignored" => ignored = true,
+                option if VALUE_OPTIONS.contains(&option) => {
+                    args.next();
+                }
+                option if option.starts_with('-') => {}
+                _ if filter.is_none() => filter = Some(arg),
+                _ => {}
+            }
+        }
+        match filter {
+            _ if ignored => Self::None,
+            None => Self::All,
+            Some(filter) if exact => Self::Exact(filter),
+            Some(filter) => match Regex::new(&filter) {
+                Ok(regex) => Self::Regex(regex),
+                Err(error) => panic!("unable to parse {filter:?} as a regular expression: {error}"),
+            },
+        }
+    }
+}
+
+/// Returns the filter of the command line.
+fn filter() -> &'static Filter {
+    static FILTER: OnceLock<Filter> = OnceLock::new();
+    FILTER.get_or_init(Filter::from_args)
+}
+
+/// Returns `true` if Criterion runs the benchmark with the full `id`,
+/// e.g. `execute/fibonacci-rec/wasmi-v2.eager.checked/30`.
+pub fn matches(id: &str) -> bool {
+    match filter() {
+        Filter::All => true,
+        Filter::Regex(regex) => regex.is_match(id),
+        Filter::Exact(exact) => id == exact,
+        Filter::None => false,
+    }
+}
+
+/// Returns `true` if Criterion may run benchmarks whose full ID starts with `prefix`,
+/// e.g. `execute/fibonacci-rec/wasmi-v2.eager.checked`.
+///
+/// The parameters of the benchmarks are unknown before they run, and a regular expression like
+/// `fibonacci-rec/.*/30` or `checked$` may match the full ID without matching the `prefix`, so
+/// only the exact filter and `--ignored` reject prefixes up front.
+pub fn may_match(prefix: &str) -> bool {
+    match filter() {
+        Filter::All | Filter::Regex(_) => true,
+        Filter::Exact(exact) => exact
+            .strip_prefix(prefix)
+            .is_some_and(|rest| rest.is_empty() || rest.starts_with('/')),
+        Filter::None => false,
+    }
+}
@@ -0,0 +1,188 @@
+use crate::execute::{
+    ALICE_TXT, ARGON2_LEN, BULK_OPS_N, CITM_CATALOG_JSON, COUNTER_GLOBAL_N, COUNTER_N, DNA_TXT,
+    FIBONACCI_ITER_N, FIBONACCI_REC_N, FIBONACCI_TAIL_N, MANDELBROT_N, MATRIX_MULTIPLY_N, NBODY_N,
+    PRIME_SIEVE_LEN, SORT_DYN_LEN, SORT_LEN, SPECTRALNORM_N,
+};
+use crate::isolate::vms_under_test;
+use crate::memory::bench_function;
+use benchmark_utils::{
+    ExecuteTestId, InputEncoding, ModuleInstance, TestId, Val, read_benchmark_file,
+};
+use criterion::{Criterion, criterion_group};
+use std::fs;
+use std::time::{Duration, Instant};
+
+criterion_group!(
+    name = bench_first_call;
+    config = Criterion::default()
+        .sample_size(10)
+        .measurement_time(Duration::from_millis(2000))
+        .warm_up_time(Duration::from_millis(1000));
+    targets = bench_first_calls,
+);
+
+/// How the `run` function of an execute test case is called, with the input of its `execute` benchmark.
+enum Input {
+    /// Calls `run(param)` directly, e.g. for the `.wat` test cases.
+    Param(Val),
+    /// Calls `setup(params)` and then `run` with the returned data.
+    Setup(Vec<Val>),
+    /// Calls `setup(len)`, writes the `len` bytes of the file at this path to `input_ptr(data)`
+    /// and then calls `run(data)`.
+    Text(&'static str),
+}
+
+/// Returns all execute test cases with the encoding of their Wasm and their [`Input`].
+fn cases() -> Vec<(ExecuteTestId, InputEncoding, Input)> {
+    use InputEncoding::{RustCompiledWasm as Rust, Wat};
+    use Val::{I32, I64};
+    vec![
+        (
+            ExecuteTestId::CounterLocal,
+            Wat,
+            Input::Param(I32(COUNTER_N)),
+        ),
+        (
+            ExecuteTestId::CounterParam,
+            Wat,
+            Input::Param(I32(COUNTER_N)),
+        ),
+        (
+            ExecuteTestId::CounterGlobal,
+            Wat,
+            Input::Param(I32(COUNTER_GLOBAL_N)),
+        ),
+        (
+            ExecuteTestId::FibonacciRec,
+            Wat,
+            Input::Param(I64(FIBONACCI_REC_N)),
+        ),
+        (
+            ExecuteTestId::FibonacciIter,
+            Wat,
+            Input::Param(I64(FIBONACCI_ITER_N)),
+        ),
+        (
+            ExecuteTestId::FibonacciTail,
+            Wat,
+            Input::Param(I64(FIBONACCI_TAIL_N)),
+        ),
+        (ExecuteTestId::BulkOps, Wat, Input::Param(I64(BULK_OPS_N))),
+        (ExecuteTestId::Sort, Rust, Input::Setup(vec![I32(SORT_LEN)])),
+        (
+            ExecuteTestId::SortDyn,
+            Rust,
+            Input::Setup(vec![I32(SORT_DYN_LEN)]),
+        ),
+        (
+            ExecuteTestId::PrimeSieve,
+            Rust,
+            Input::Setup(vec![I64(PRIME_SIEVE_LEN)]),
+        ),
+        (
+            ExecuteTestId::MatrixMultiply,
+            Rust,
+            Input::Setup(vec![I32(MATRIX_MULTIPLY_N)]),
+        ),
+        (ExecuteTestId::Nbody, Rust, Input::Setup(vec![I32(NBODY_N)])),
+        (
+            ExecuteTestId::Argon2,
+            Rust,
+            Input::Setup(vec![I32(ARGON2_LEN)]),
+        ),
+        (ExecuteTestId::TinyKeccak, Rust, Input::Setup(vec![])),
+        (
+            ExecuteTestId::Mandelbrot,
+            Rust,
+            Input::Setup(vec![I32(MANDELBROT_N)]),
+        ),
+        (
+            ExecuteTestId::Spectralnorm,
+            Rust,
+            Input::Setup(vec![I32(SPECTRALNORM_N)]),
+        ),
+        (ExecuteTestId::Compression, Rust, Input::Text(ALICE_TXT)),
+        (ExecuteTestId::WordCount, Rust, Input::Text(ALICE_TXT)),
+        (
+            ExecuteTestId::JsonParse,
+            Rust,
+            Input::Text(CITM_CATALOG_JSON),
+        ),
+        (ExecuteTestId::ReverseComplement, Rust, Input::Text(DNA_TXT)),
+        (ExecuteTestId::RegexRedux, Rust, Input::Text(DNA_TXT)),
+    ]
+}
+
+impl Input {
+    /// Prepares the freshly instantiated `instance` and returns the parameters of its `run` call.
+    ///
+    /// The `text` is the content of the file of [`Input::Text`] and empty otherwise.
+    fn prepare(&self, instance: &mut dyn ModuleInstance, text: &[u8]) -> Vec<Val> {
+        let setup = |instance: &mut dyn ModuleInstance, params: &[Val]| {
+            let mut data = [Val::I32(0)];
+            instance.call("setup", params, &mut data).unwrap();
+            data[0]
+        };
+        match self {
+            Input::Param(param) => vec![*param],
+            Input::Setup(params) => vec![setup(instance, params)],
+            Input::Text(_) => {
+                let data = setup(instance, &[Val::I32(text.len() as i32)]);
+                let mut input_ptr = [Val::I32(0)];
+                instance.call("input_ptr", &[data], &mut input_ptr).unwrap();
+                let input_ptr = input_ptr[0].unwrap_i32() as u32;
+                instance.write_memory("memory", input_ptr, text).unwrap();
+                vec![data]
+            }
+        }
+    }
+
+    /// Returns the results buffer of the `run` call.
+    fn results(&self) -> Vec<Val> {
+        match self {
+            // The `run` functions of the `.wat` test cases return a value of their parameter's type.
+            Input::Param(param) => vec![Val::default_for_ty(param.ty())],
+            Input::Setup(_) | Input::Text(_) => Vec::new(),
+        }
+    }
+}
+
+/// Benchmarks instantiating every execute test case and calling its `run` function once.
+///
+/// Runs on all compilation modes: lazy modes shift costs from instantiation to the first
+/// call, so only the sum of both shows their end-to-end trade-off. Only instantiation and
+/// the `run` call are timed, not the calls preparing its input.
+fn bench_first_calls(c: &mut Criterion) {
+    for (id, encoding, input) in cases() {
+        let wasm = read_benchmark_file(encoding, id.into());
+        let text = match &input {
+            Input::Text(path) => fs::read(path).unwrap(),
+            Input::Param(_) | Input::Setup(_) => Vec::new(),
+        };
+        let group = format!("first-call/{id}");
+        let mut g = c.benchmark_group(&group);
+        for vm in vms_under_test(&group) {
+            let Some(rt) = vm.setup(TestId::FirstCall(id)) else {
+                continue;
+            };
+            let bench_id = vm.id().to_string();
+            let full_id = format!("first-call/{id}/{bench_id}");
+            bench_function(&mut g, &bench_id, &full_id, |b| {
+                b.iter_custom(|iterations| {
+                    let mut elapsed = Duration::ZERO;
+                    for _ in 0..iterations {
+                        let started = Instant::now();
+                        let mut instance = rt.instantiate(&wasm[..]);
+                        elapsed += started.elapsed();
+                        let params = input.prepare(&mut *instance, &text);
+                        let mut results = input.results();
+                        let started = Instant::now();
+                        instance.call("run", &params, &mut results).unwrap();
+                        elapsed += started.elapsed();
+                    }
+                    elapsed
+                })
+            });
+        }
+    }
+}
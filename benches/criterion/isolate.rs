@@ -0,0 +1,150 @@
+//! Runs every runtime on every test case in a fresh child process.
+//!
+//! All runtimes are linked into the same binary. Run sequentially in one process, they can bias
+//! the runtimes benchmarked after them, e.g. via V8's platform threads, the heap fragmentation
+//! of SpaceWasm or the virtual memory reserved by Wasmtime. Setting `$WASMI_BENCHMARKS_ISOLATE`
+//! spawns the current benchmark binary once per `(runtime, test case)` pair instead, selecting
+//! the pair via `$WASMI_BENCHMARKS_SELECT`. Each child writes its results to its own file, which
+//! the parent merges into the file in `$WASMI_BENCHMARKS_JSON`.
+
+use crate::filter;
+use crate::report::{RESULTS_VAR, merge_results};
+use benchmark_utils::Runtime;
+use std::env;
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, ExitCode};
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// The environment variable enabling the process per runtime and test case isolation.
+pub const ISOLATE_VAR: &str = "WASMI_BENCHMARKS_ISOLATE";
+
+/// The environment variable selecting the only `{category}/{case}/{runtime}` a child benchmarks.
+const SELECT_VAR: &str = "WASMI_BENCHMARKS_SELECT";
+
+/// The environment variable adding the metered runtimes to the `execute` benchmarks.
+///
+/// See [`wasmi_benchmarks::metered_vms_under_test`].
+const METERING_VAR: &str = "WASMI_BENCHMARKS_METERING";
+
+/// Whether [`vms_under_test`] only lists the pairs to benchmark instead of benchmarking them.
+static LISTING: AtomicBool = AtomicBool::new(false);
+
+/// The `(group, runtime)` pairs collected while [`LISTING`].
+static LISTED: Mutex<Vec<(String, String)>> = Mutex::new(Vec::new());
+
+/// Returns `true` if the current process runs isolated children instead of benchmarks.
+pub fn is_parent() -> bool {
+    env::var_os(ISOLATE_VAR).is_some() && env::var_os(SELECT_VAR).is_none()
+}
+
+/// Returns `true` if the current process is an isolated child benchmarking a single pair.
+pub fn is_child() -> bool {
+    env::var_os(SELECT_VAR).is_some()
+}
+
+/// Returns the Wasm runtimes to benchmark on the test cases of the benchmark `group`,
+/// e.g. `execute/fibonacci-rec`.
+///
+/// Within an isolated child these are only the selected runtime if `group` is the selected
+/// group, and no runtime at all otherwise. Outside of isolation these are all runtimes.
+///
+/// With `$WASMI_BENCHMARKS_METERING` set, the `execute` groups also cover all runtimes with
+/// every metering they support, to compare their performance with metering on and off.
+pub fn vms_under_test(group: &str) -> Vec<Box<dyn Runtime>> {
+    let mut vms = wasmi_benchmarks::vms_under_test();
+    if group.starts_with("execute/") && env::var_os(METERING_VAR).is_some() {
+        vms.extend(wasmi_benchmarks::metered_vms_under_test());
+    }
+    if LISTING.load(Ordering::Relaxed) {
+        let mut listed = LISTED.lock().unwrap_or_else(|error| error.into_inner());
+        listed.extend(
+            vms.iter()
+                .map(|vm| (group.to_string(), vm.id().to_string())),
+        );
+        return Vec::new();
+    }
+    let Ok(selected) = env::var(SELECT_VAR) else {
+        return vms;
+    };
+    vms.into_iter()
+        .filter(|vm| selected == format!("{group}/{}", vm.id()))
+        .collect()
+}
+
+/// Runs `benchmarks` in a fresh child process for every `(runtime, test case)` pair it covers.
+///
+/// Pairs that Criterion's exact benchmark filter excludes are skipped without spawning a child.
+/// The children inherit the command line arguments, so Criterion's filters and options apply
+/// to all of them. A child that crashes is reported and does not abort the remaining pairs.
+/// Writes the merged results of all children to `$WASMI_BENCHMARKS_JSON` if set.
+pub fn run_isolated(benchmarks: impl FnOnce()) -> ExitCode {
+    // Runs all benchmark functions without benchmarking anything to list the pairs.
+    LISTING.store(true, Ordering::Relaxed);
+    benchmarks();
+    LISTING.store(false, Ordering::Relaxed);
+    let mut pairs = std::mem::take(&mut *LISTED.lock().unwrap_or_else(|error| error.into_inner()));
+    pairs.retain(|(group, runtime)| filter::may_match(&format!("{group}/{runtime}")));
+    if pairs.is_empty() {
+        eprintln!("no isolated benchmark matches the filter");
+    }
+    let results = env::var_os(RESULTS_VAR).map(PathBuf::from);
+    let mut parts = Vec::new();
+    let mut failures = 0;
+    for (index, (group, runtime)) in pairs.iter().enumerate() {
+        let selected = format!("{group}/{runtime}");
+        let part = results.as_ref().map(|_| part_path(index));
+        match run_child(&selected, part.as_deref()) {
+            Ok(()) => parts.extend(part),
+            Err(error) => {
+                eprintln!("isolated benchmark of {selected} failed: {error}");
+                failures += 1;
+                // A failed child writes no results, or incomplete ones if writing them failed.
+                if let Some(part) = part {
+                    let _ = fs::remove_file(part);
+                }
+            }
+        }
+    }
+    let mut exit_code = match failures {
+        0 => ExitCode::SUCCESS,
+        _ => ExitCode::FAILURE,
+    };
+    if let Some(path) = results {
+        if let Err(error) = merge_results(&path, &parts) {
+            eprintln!("failed to write benchmark results to ${RESULTS_VAR}: {error}");
+            exit_code = ExitCode::FAILURE;
+        }
+        for part in &parts {
+            let _ = fs::remove_file(part);
+        }
+    }
+    exit_code
+}
+
+/// Returns the path of the results file of the child with `index`.
+fn part_path(index: usize) -> PathBuf {
+    env::temp_dir().join(format!(
+        "wasmi-benchmarks-{}-{index}.jsonl",
+        std::process::id()
+    ))
+}
+
+/// Runs the benchmarks of the `selected` pair in a child process, writing results to `part`.
+fn run_child(selected: &str, part: Option<&Path>) -> Result<(), Box<dyn Error>> {
+    let mut command = Command::new(env::current_exe()?);
+    command
+        .args(env::args_os().skip(1))
+        .env(SELECT_VAR, selected)
+        .env_remove(RESULTS_VAR);
+    if let Some(part) = part {
+        command.env(RESULTS_VAR, part);
+    }
+    let status = command.status()?;
+    if !status.success() {
+        return Err(format!("child process exited with {status}").into());
+    }
+    Ok(())
+}
@@ -1,8 +1,62 @@
+mod cold_start;
 mod execute;
+mod filter;
+mod first_call;
+mod isolate;
+mod memory;
+mod perf;
+mod report;
 mod startup;
 
+use self::cold_start::bench_cold_start;
 use self::execute::bench_execute;
+use self::first_call::bench_first_call;
+use self::report::{RESULTS_VAR, write_results};
 use self::startup::bench_startup;
-use criterion::criterion_main;
+use criterion::Criterion;
+use std::env;
+use std::path::Path;
+use std::process::ExitCode;
+use std::time::SystemTime;
 
-criterion_main!(bench_execute, bench_startup);
+/// Runs all benchmarks like `criterion_main!` would.
+///
+/// Additionally writes the results as JSON lines to the path in `$WASMI_BENCHMARKS_JSON`
+/// if set, so that plain `cargo bench` works without `cargo criterion`. That includes the
+/// hardware performance counters of the execute benchmarks if `$WASMI_BENCHMARKS_PERF` is set.
+///
+/// Measures the peak memory usage of every benchmark instead of its time if
+/// `$WASMI_BENCHMARKS_MEMORY` is set, which requires `$WASMI_BENCHMARKS_JSON` to be set.
+///
+/// Runs every runtime on every test case in its own process if `$WASMI_BENCHMARKS_ISOLATE` is set.
+fn main() -> ExitCode {
+    if isolate::is_parent() {
+        return isolate::run_isolated(|| {
+            bench_execute();
+            bench_startup();
+            bench_cold_start();
+            bench_first_call();
+        });
+    }
+    let started = SystemTime::now();
+    if env::var_os(memory::MEMORY_VAR).is_some() {
+        if env::var_os(RESULTS_VAR).is_none() {
+            eprintln!("${} requires ${RESULTS_VAR} to be set", memory::MEMORY_VAR);
+            return ExitCode::FAILURE;
+        }
+        memory::enable();
+    }
+    bench_execute();
+    bench_startup();
+    bench_cold_start();
+    bench_first_call();
+    Criterion::default().configure_from_args().final_summary();
+    let Some(path) = env::var_os(RESULTS_VAR) else {
+        return ExitCode::SUCCESS;
+    };
+    if let Err(error) = write_results(Path::new(&path), started) {
+        eprintln!("failed to write benchmark results to ${RESULTS_VAR}: {error}");
+        return ExitCode::FAILURE;
+    }
+    ExitCode::SUCCESS
+}
@@ -0,0 +1,238 @@
+//! Measures the peak memory usage of the Wasm runtimes in a pass separate from the timed one.
+//!
+//! Setting `$WASMI_BENCHMARKS_MEMORY` replaces the timed benchmarks by the memory pass: every
+//! benchmark selected by Criterion's filter runs its setup followed by [`ITERATIONS`] calls of
+//! its routine outside of Criterion, so the bookkeeping never affects time measurements.
+//! Two complementary quantities are recorded per benchmark:
+//!
+//! - The peak number of heap bytes allocated through Rust's global allocator, counted by
+//!   [`CountingAllocator`]. This covers all runtimes written in Rust, including SpaceWasm,
+//!   whose own allocator hooks forward to `std::alloc` and thus to the global allocator, which
+//!   is why its `MemoryStatistics` are not queried in addition.
+//! - The peak growth of the resident set size (RSS) of the process, which also covers
+//!   runtimes written in C or C++ that allocate through `malloc` or `mmap` directly, e.g.
+//!   Wasm3, WAMR or V8. Only available on Linux, where the peak RSS can be reset.
+//!
+//! Both cover the setup of a benchmark, e.g. instantiating its module, and its calls.
+
+use crate::filter;
+use criterion::measurement::WallTime;
+use criterion::{Bencher, BenchmarkGroup};
+use serde_json as json;
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::fs;
+use std::hint::black_box;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::time::Duration;
+
+/// The environment variable running the memory pass instead of the timed benchmarks.
+pub const MEMORY_VAR: &str = "WASMI_BENCHMARKS_MEMORY";
+
+/// The number of calls of a benchmark's routine after its setup in the memory pass.
+const ITERATIONS: u64 = 3;
+
+/// Whether the memory pass runs and allocations are counted, see [`enable`].
+static ENABLED: AtomicBool = AtomicBool::new(false);
+/// The number of currently allocated heap bytes while counting is enabled.
+static CURRENT: AtomicUsize = AtomicUsize::new(0);
+/// The peak of [`CURRENT`] since the last reset.
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+/// The peak memory usage recorded for every benchmark by its full ID.
+static USAGES: Mutex<Vec<(String, Usage)>> = Mutex::new(Vec::new());
+
+/// Wraps the system allocator to count the allocated heap bytes.
+///
+/// Counting is disabled outside of the memory pass, which leaves a single relaxed atomic
+/// load per allocation in the timed benchmarks.
+struct CountingAllocator;
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+impl CountingAllocator {
+    /// Records that `size` more bytes are allocated.
+    fn grow(size: usize) {
+        if ENABLED.load(Ordering::Relaxed) {
+            let current = CURRENT.fetch_add(size, Ordering::Relaxed) + size;
+            PEAK.fetch_max(current, Ordering::Relaxed);
+        }
+    }
+
+    /// Records that `size` fewer bytes are allocated.
+    fn shrink(size: usize) {
+        if ENABLED.load(Ordering::Relaxed) {
+            // Memory allocated before counting was enabled may be freed afterwards.
+            let _ = CURRENT.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |current| {
+                Some(current.saturating_sub(size))
+            });
+        }
+    }
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc(layout) };
+        if !ptr.is_null() {
+            Self::grow(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc_zeroed(layout) };
+        if !ptr.is_null() {
+            Self::grow(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) };
+        Self::shrink(layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = unsafe { System.realloc(ptr, layout, new_size) };
+        if !new_ptr.is_null() {
+            Self::shrink(layout.size());
+            Self::grow(new_size);
+        }
+        new_ptr
+    }
+}
+
+/// Runs the memory pass instead of the timed benchmarks for all benchmarks run afterwards.
+pub fn enable() {
+    ENABLED.store(true, Ordering::Relaxed);
+}
+
+/// Returns `true` if the memory pass runs instead of the timed benchmarks.
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Runs the routine of a benchmark, either timed by Criterion or in the memory pass.
+pub enum Runner<'a, 'b> {
+    /// Times the routine with Criterion.
+    Timed(&'a mut Bencher<'b>),
+    /// Calls the routine [`ITERATIONS`] times without timing it.
+    Memory,
+}
+
+impl Runner<'_, '_> {
+    /// Runs `routine` like [`Bencher::iter`].
+    pub fn iter<O>(&mut self, mut routine: impl FnMut() -> O) {
+        match self {
+            Self::Timed(b) => b.iter(routine),
+            Self::Memory => {
+                for _ in 0..ITERATIONS {
+                    black_box(routine());
+                }
+            }
+        }
+    }
+
+    /// Runs `routine` like [`Bencher::iter_custom`].
+    pub fn iter_custom(&mut self, mut routine: impl FnMut(u64) -> Duration) {
+        match self {
+            Self::Timed(b) => b.iter_custom(routine),
+            Self::Memory => {
+                routine(ITERATIONS);
+            }
+        }
+    }
+}
+
+/// Benchmarks `f` as `bench_id` of `g` with the full ID `full_id`,
+/// e.g. `execute/fibonacci-rec/wasmi-v2.eager.checked/30`.
+///
+/// In the memory pass `f` runs once outside of Criterion instead, if Criterion's filter
+/// selects `full_id`, and its peak memory usage is recorded.
+pub fn bench_function(
+    g: &mut BenchmarkGroup<WallTime>,
+    bench_id: &str,
+    full_id: &str,
+    mut f: impl FnMut(&mut Runner),
+) {
+    if !is_enabled() {
+        g.bench_function(bench_id, |b| f(&mut Runner::Timed(b)));
+        return;
+    }
+    if filter::matches(full_id) {
+        measure(full_id, || f(&mut Runner::Memory));
+    }
+}
+
+/// The peak memory usage of a benchmark in bytes.
+#[derive(Debug, Copy, Clone, Default)]
+struct Usage {
+    /// The peak number of allocated heap bytes.
+    heap_peak: usize,
+    /// The peak growth of the resident set size, if available.
+    rss_peak: Option<usize>,
+}
+
+/// Reads a `{field}: {value} kB` entry of `/proc/self/status` in bytes.
+fn read_proc_status(field: &str) -> Option<usize> {
+    let status = fs::read_to_string("/proc/self/status").ok()?;
+    let line = status.lines().find(|line| line.starts_with(field))?;
+    let kib = line[field.len()..]
+        .trim_start_matches(':')
+        .trim()
+        .trim_end_matches("kB")
+        .trim()
+        .parse::<usize>()
+        .ok()?;
+    Some(kib * 1024)
+}
+
+/// Resets the peak resident set size of the process to its current resident set size.
+///
+/// Returns the current resident set size in bytes, or `None` if the peak cannot be reset.
+fn reset_rss_peak() -> Option<usize> {
+    // Writing `5` resets the `VmHWM` peak RSS, see `man 5 proc`.
+    fs::write("/proc/self/clear_refs", "5").ok()?;
+    read_proc_status("VmRSS")
+}
+
+/// Runs `f` and records its peak memory usage for the benchmark with the full ID `id`.
+///
+/// Keeps the maximum if `id` has been measured before.
+fn measure(id: &str, f: impl FnOnce()) {
+    let rss_before = reset_rss_peak();
+    let heap_before = CURRENT.load(Ordering::Relaxed);
+    PEAK.store(heap_before, Ordering::Relaxed);
+    f();
+    let usage = Usage {
+        heap_peak: PEAK.load(Ordering::Relaxed).saturating_sub(heap_before),
+        rss_peak: rss_before
+            .zip(read_proc_status("VmHWM"))
+            .map(|(before, peak)| peak.saturating_sub(before)),
+    };
+    let mut usages = USAGES.lock().unwrap_or_else(|error| error.into_inner());
+    match usages.iter_mut().find(|(measured, _)| measured == id) {
+        Some((_, max)) => {
+            max.heap_peak = max.heap_peak.max(usage.heap_peak);
+            max.rss_peak = max.rss_peak.max(usage.rss_peak);
+        }
+        None => usages.push((id.to_string(), usage)),
+    }
+}
+
+/// Encodes the `memory-complete` messages of all measured benchmarks.
+pub fn encode_memory_usages() -> Vec<json::Value> {
+    let usages = USAGES.lock().unwrap_or_else(|error| error.into_inner());
+    usages
+        .iter()
+        .map(|(id, usage)| {
+            json::json!({
+                "reason": "memory-complete",
+                "id": id,
+                "heap_peak": usage.heap_peak,
+                "rss_peak": usage.rss_peak,
+            })
+        })
+        .collect()
+}
@@ -0,0 +1,296 @@
+//! Counts hardware events such as retired instructions while the execute benchmarks run.
+//!
+//! Enabled by setting `$WASMI_BENCHMARKS_PERF` on Linux, where the counters are read via
+//! `perf_event_open`. Hardware counters are far less affected by noisy neighbors than wall-clock
+//! time, which makes them useful on shared CI machines and for tuning interpreter dispatch.
+//! The counters only include events of the benchmarking thread in user space.
+//!
+//! All events are counted as one group, so that the CPU schedules them together. If the CPU has
+//! fewer counters than events the kernel multiplexes the group with other groups, in which case
+//! the counts are scaled up by the time the group was enabled over the time it actually ran.
+
+use crate::memory::Runner;
+use serde_json as json;
+use std::env;
+use std::hint::black_box;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Instant;
+
+/// The environment variable enabling the hardware performance counters.
+pub const PERF_VAR: &str = "WASMI_BENCHMARKS_PERF";
+
+/// The counted hardware events with their names in the `perf-complete` messages.
+const EVENTS: [(Event, &str); 4] = [
+    (Event::Instructions, "instructions"),
+    (Event::Cycles, "cycles"),
+    (Event::BranchMisses, "branch_misses"),
+    (Event::CacheMisses, "cache_misses"),
+];
+
+/// A hardware event counted by the CPU.
+#[derive(Debug, Copy, Clone)]
+enum Event {
+    Instructions,
+    Cycles,
+    BranchMisses,
+    CacheMisses,
+}
+
+/// Whether opening the counters failed before, so that the failure is only reported once.
+static UNAVAILABLE: AtomicBool = AtomicBool::new(false);
+
+/// The summed event counts and iterations of every benchmark by its full ID.
+static COUNTS: Mutex<Vec<(String, Counts)>> = Mutex::new(Vec::new());
+
+/// The summed event counts of all measured iterations of a benchmark.
+#[derive(Debug, Copy, Clone, Default)]
+struct Counts {
+    /// The number of measured iterations.
+    iterations: u64,
+    /// The summed counts of the [`EVENTS`] in the same order.
+    events: [u64; EVENTS.len()],
+}
+
+/// Times `routine` like [`criterion::Bencher::iter`] and counts its hardware events if enabled.
+///
+/// The counts are recorded for the benchmark with the full ID `id`, e.g.
+/// `execute/fibonacci-rec/wasmi-v2.eager.checked/30`, and summed up over all invocations.
+/// Nothing is counted in the memory pass.
+pub fn iter<O>(runner: &mut Runner, id: &str, mut routine: impl FnMut() -> O) {
+    let Runner::Timed(b) = runner else {
+        return runner.iter(routine);
+    };
+    let Some(mut counters) = Counters::open() else {
+        return b.iter(routine);
+    };
+    b.iter_custom(|iterations| {
+        counters.start();
+        let started = Instant::now();
+        for _ in 0..iterations {
+            black_box(routine());
+        }
+        let elapsed = started.elapsed();
+        let Some(events) = counters.stop() else {
+            return elapsed;
+        };
+        let mut counts = COUNTS.lock().unwrap_or_else(|error| error.into_inner());
+        let index = match counts.iter().position(|(counted, _)| counted == id) {
+            Some(index) => index,
+            None => {
+                counts.push((id.to_string(), Counts::default()));
+                counts.len() - 1
+            }
+        };
+        let total = &mut counts[index].1;
+        total.iterations += iterations;
+        for (total, count) in total.events.iter_mut().zip(events) {
+            *total += count;
+        }
+        elapsed
+    });
+}
+
+/// Encodes the `perf-complete` messages with the event counts per iteration of all benchmarks.
+pub fn encode_perf_counts() -> Vec<json::Value> {
+    let counts = COUNTS.lock().unwrap_or_else(|error| error.into_inner());
+    counts
+        .iter()
+        .filter(|(_, counts)| counts.iterations != 0)
+        .map(|(id, counts)| {
+            let mut message = json::json!({
+                "reason": "perf-complete",
+                "id": id,
+                "iterations": counts.iterations,
+            });
+            for ((_, name), count) in EVENTS.iter().zip(counts.events) {
+                message[name] = json::json!(count as f64 / counts.iterations as f64);
+            }
+            message
+        })
+        .collect()
+}
+
+/// The opened hardware performance counters of the current thread.
+#[cfg(target_os = "linux")]
+struct Counters {
+    /// The file descriptors of the counters of the [`EVENTS`] in the same order.
+    ///
+    /// The first counter is the leader of the group of all counters.
+    fds: [libc::c_int; EVENTS.len()],
+}
+
+/// The `perf_event_attr` of the `perf_event_open` system call, see `man 2 perf_event_open`.
+#[cfg(target_os = "linux")]
+#[repr(C)]
+#[derive(Default)]
+struct PerfEventAttr {
+    kind: u32,
+    size: u32,
+    config: u64,
+    sample_period: u64,
+    sample_type: u64,
+    read_format: u64,
+    flags: u64,
+    wakeup_events: u32,
+    bp_type: u32,
+    config1: u64,
+    config2: u64,
+    branch_sample_type: u64,
+    sample_regs_user: u64,
+    sample_stack_user: u32,
+    clockid: i32,
+    sample_regs_intr: u64,
+    aux_watermark: u32,
+    sample_max_stack: u16,
+    reserved_2: u16,
+    aux_sample_size: u32,
+    reserved_3: u32,
+    sig_data: u64,
+    config3: u64,
+}
+
+#[cfg(target_os = "linux")]
+impl Counters {
+    /// The `PERF_TYPE_HARDWARE` event type.
+    const TYPE_HARDWARE: u32 = 0;
+    /// The `disabled` flag, only set for the group leader so that it controls the group.
+    const DISABLED: u64 = 1 << 0;
+    /// The `exclude_kernel` and `exclude_hv` flags.
+    const FLAGS: u64 = (1 << 5) | (1 << 6);
+    /// The `PERF_FORMAT_TOTAL_TIME_ENABLED`, `PERF_FORMAT_TOTAL_TIME_RUNNING` and
+    /// `PERF_FORMAT_GROUP` read format.
+    const READ_FORMAT: u64 = (1 << 0) | (1 << 1) | (1 << 3);
+    /// The `PERF_IOC_FLAG_GROUP` argument applying an `ioctl` to the whole group.
+    const IOC_FLAG_GROUP: libc::c_int = 1;
+    /// The `PERF_EVENT_IOC_ENABLE` request.
+    const IOC_ENABLE: libc::c_ulong = 0x2400;
+    /// The `PERF_EVENT_IOC_DISABLE` request.
+    const IOC_DISABLE: libc::c_ulong = 0x2401;
+    /// The `PERF_EVENT_IOC_RESET` request.
+    const IOC_RESET: libc::c_ulong = 0x2403;
+
+    /// Opens the counters of all [`EVENTS`] as one group if `$WASMI_BENCHMARKS_PERF` is set.
+    ///
+    /// Returns `None` if disabled or if the counters cannot be opened, e.g. because
+    /// `/proc/sys/kernel/perf_event_paranoid` forbids it, in which case a warning is printed once.
+    fn open() -> Option<Self> {
+        env::var_os(PERF_VAR)?;
+        if UNAVAILABLE.load(Ordering::Relaxed) {
+            return None;
+        }
+        let mut fds = [-1; EVENTS.len()];
+        for index in 0..EVENTS.len() {
+            let (event, name) = EVENTS[index];
+            let leader = fds[0];
+            let attr = PerfEventAttr {
+                kind: Self::TYPE_HARDWARE,
+                size: size_of::<PerfEventAttr>() as u32,
+                config: match event {
+                    Event::Cycles => 0,
+                    Event::Instructions => 1,
+                    Event::CacheMisses => 3,
+                    Event::BranchMisses => 5,
+                },
+                read_format: Self::READ_FORMAT,
+                flags: match index {
+                    0 => Self::DISABLED | Self::FLAGS,
+                    _ => Self::FLAGS,
+                },
+                ..PerfEventAttr::default()
+            };
+            // SAFETY: `attr` is a valid `perf_event_attr` that outlives the system call.
+            let opened = unsafe {
+                libc::syscall(
+                    libc::SYS_perf_event_open,
+                    &attr as *const PerfEventAttr,
+                    0,
+                    -1,
+                    leader,
+                    0,
+                )
+            };
+            if opened < 0 {
+                let error = std::io::Error::last_os_error();
+                UNAVAILABLE.store(true, Ordering::Relaxed);
+                eprintln!("failed to open the {name} counter, see ${PERF_VAR}: {error}");
+                Self::close(&fds);
+                return None;
+            }
+            fds[index] = opened as libc::c_int;
+        }
+        Some(Self { fds })
+    }
+
+    /// Resets and enables all counters.
+    fn start(&mut self) {
+        let leader = self.fds[0];
+        // SAFETY: `leader` is an open perf event file descriptor.
+        unsafe {
+            libc::ioctl(leader, Self::IOC_RESET, Self::IOC_FLAG_GROUP);
+            libc::ioctl(leader, Self::IOC_ENABLE, Self::IOC_FLAG_GROUP);
+        }
+    }
+
+    /// Disables all counters and returns their counts, scaled up if the group was multiplexed.
+    ///
+    /// Returns `None` if the counts cannot be read or if the group never ran, e.g. because other
+    /// groups occupied all counters of the CPU.
+    fn stop(&mut self) -> Option<[u64; EVENTS.len()]> {
+        let leader = self.fds[0];
+        // SAFETY: `leader` is an open perf event file descriptor.
+        unsafe { libc::ioctl(leader, Self::IOC_DISABLE, Self::IOC_FLAG_GROUP) };
+        // The number of counters, the times enabled and running and the count of every counter.
+        let mut buffer = [0_u64; 3 + EVENTS.len()];
+        // SAFETY: reading the group leader yields the `PERF_FORMAT_GROUP` layout of `u64`s.
+        let read = unsafe { libc::read(leader, buffer.as_mut_ptr().cast(), size_of_val(&buffer)) };
+        let [counters, enabled, running, values @ ..] = buffer;
+        if read != size_of_val(&buffer) as isize || counters != EVENTS.len() as u64 || running == 0
+        {
+            return None;
+        }
+        if enabled == running {
+            return Some(values);
+        }
+        let scale = enabled as f64 / running as f64;
+        Some(values.map(|value| (value as f64 * scale) as u64))
+    }
+
+    /// Closes all opened file descriptors of `fds`.
+    fn close(fds: &[libc::c_int]) {
+        for &fd in fds.iter().filter(|&&fd| fd >= 0) {
+            // SAFETY: `fd` is an open perf event file descriptor owned by the counters.
+            unsafe { libc::close(fd) };
+        }
+    }
+}
+
+#[cfg(target_os = "linux")]
+impl Drop for Counters {
+    fn drop(&mut self) {
+        Self::close(&self.fds);
+    }
+}
+
+/// Hardware performance counters are only supported on Linux.
+#[cfg(not(target_os = "linux"))]
+struct Counters;
+
+#[cfg(not(target_os = "linux"))]
+impl Counters {
+    /// Warns once that `$WASMI_BENCHMARKS_PERF` is unsupported if set.
+    fn open() -> Option<Self> {
+        env::var_os(PERF_VAR)?;
+        if UNAVAILABLE.swap(true, Ordering::Relaxed) {
+            return None;
+        }
+        eprintln!("hardware performance counters are only supported on Linux, see ${PERF_VAR}");
+        None
+    }
+
+    fn start(&mut self) {}
+
+    fn stop(&mut self) -> Option<[u64; EVENTS.len()]> {
+        None
+    }
+}
@@ -0,0 +1,453 @@
+//! Writes the benchmark results as JSON lines without the need for `cargo criterion`.
+//!
+//! The messages follow the schema of `cargo criterion --message-format=json` as stored
+//! under `data/*/criterion.json` and are assembled from the files Criterion saves for
+//! every benchmark under `target/criterion/{benchmark}/new`.
+
+use crate::isolate;
+use crate::memory::{self, encode_memory_usages};
+use crate::perf::encode_perf_counts;
+use benchmark_utils::{ModuleStats, RuntimeKind};
+use serde_json as json;
+use std::env;
+use std::error::Error;
+use std::fs;
+use std::io::{BufWriter, Write as _};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+use wasmi_benchmarks::{metered_vms_under_test, vms_under_test};
+
+/// The environment variable holding the path of the JSON lines file to write.
+pub const RESULTS_VAR: &str = "WASMI_BENCHMARKS_JSON";
+
+/// The [`ModuleStats`] of the Wasm modules of the startup test cases by benchmark group.
+static MODULES: Mutex<Vec<(String, ModuleStats)>> = Mutex::new(Vec::new());
+
+/// Records the [`ModuleStats`] of the Wasm module benchmarked by `group`, e.g. `startup/bz2`.
+pub fn record_module(group: &str, stats: ModuleStats) {
+    let mut modules = MODULES.lock().unwrap_or_else(|error| error.into_inner());
+    modules.push((group.to_string(), stats));
+}
+
+/// The sizes of the artifacts compiled from the startup modules by full benchmark ID.
+static COMPILED_SIZES: Mutex<Vec<(String, Option<usize>)>> = Mutex::new(Vec::new());
+
+/// Records the size of the artifact the runtime of the startup benchmark `id` compiles,
+/// e.g. of `startup/bz2/wasmtime.cranelift`, as returned by `compiled_size`.
+///
+/// Calls `compiled_size` only once per benchmark and only if the results of the timed
+/// benchmarks are written to `$WASMI_BENCHMARKS_JSON`, since compiling large modules takes a while.
+pub fn record_compiled_size(id: &str, compiled_size: impl FnOnce() -> Option<usize>) {
+    if env::var_os(RESULTS_VAR).is_none() || memory::is_enabled() {
+        return;
+    }
+    let mut sizes = COMPILED_SIZES
+        .lock()
+        .unwrap_or_else(|error| error.into_inner());
+    if sizes.iter().any(|(recorded, _)| recorded == id) {
+        return;
+    }
+    sizes.push((id.to_string(), compiled_size()));
+}
+
+/// Encodes the `compiled-size` messages of all runtimes that expose their compiled sizes.
+fn encode_compiled_sizes() -> Vec<json::Value> {
+    let sizes = COMPILED_SIZES
+        .lock()
+        .unwrap_or_else(|error| error.into_inner());
+    sizes
+        .iter()
+        .filter_map(|(id, size)| {
+            Some(json::json!({
+                "reason": "compiled-size",
+                "id": id,
+                "compiled_size": (*size)?,
+            }))
+        })
+        .collect()
+}
+
+/// Encodes the `module-info` messages of the recorded modules of `groups`.
+fn encode_module_infos(groups: &[&str]) -> Vec<json::Value> {
+    let modules = MODULES.lock().unwrap_or_else(|error| error.into_inner());
+    modules
+        .iter()
+        .filter(|(group, _)| groups.contains(&group.as_str()))
+        .map(|(group, stats)| {
+            json::json!({
+                "reason": "module-info",
+                "group": group,
+                "module_size": stats.module_size,
+                "code_size": stats.code_size,
+                "funcs": stats.funcs,
+            })
+        })
+        .collect()
+}
+
+/// Returns the directory into which Criterion saves its results.
+///
+/// Mirrors Criterion's own lookup: `$CRITERION_HOME`, `$CARGO_TARGET_DIR/criterion`
+/// or `target/criterion` relative to the package root that `cargo bench` runs in.
+fn criterion_dir() -> PathBuf {
+    if let Some(home) = env::var_os("CRITERION_HOME") {
+        return PathBuf::from(home);
+    }
+    match env::var_os("CARGO_TARGET_DIR") {
+        Some(target) => Path::new(&target).join("criterion"),
+        None => PathBuf::from("target/criterion"),
+    }
+}
+
+/// A single benchmark result saved by Criterion.
+struct Saved {
+    /// The `new` directory of the benchmark.
+    dir: PathBuf,
+    /// The time at which the benchmark result was saved.
+    modified: SystemTime,
+    /// The decoded `benchmark.json` holding the benchmark ID.
+    benchmark: json::Value,
+}
+
+impl Saved {
+    /// Reads and decodes the JSON file `name` of the benchmark.
+    fn read(&self, name: &str) -> Result<json::Value, Box<dyn Error>> {
+        let path = self.dir.join(name);
+        let contents = fs::read_to_string(&path)
+            .map_err(|error| format!("failed to read {}: {error}", path.display()))?;
+        Ok(json::from_str(&contents)?)
+    }
+
+    /// Returns the full ID of the benchmark, e.g. `execute/fibonacci-rec/wasmi-v2.eager.checked/30`.
+    fn id(&self) -> Result<&str, Box<dyn Error>> {
+        self.benchmark
+            .get("full_id")
+            .and_then(json::Value::as_str)
+            .ok_or_else(|| format!("missing `full_id` in {}", self.dir.display()).into())
+    }
+
+    /// Returns the ID of the benchmark group, e.g. `execute/fibonacci-rec`.
+    fn group(&self) -> Result<&str, Box<dyn Error>> {
+        self.benchmark
+            .get("group_id")
+            .and_then(json::Value::as_str)
+            .ok_or_else(|| format!("missing `group_id` in {}", self.dir.display()).into())
+    }
+
+    /// Returns the directory of the benchmark's HTML report.
+    fn report_directory(&self) -> String {
+        let benchmark = self.dir.parent().unwrap_or(&self.dir);
+        benchmark.join("report").display().to_string()
+    }
+}
+
+/// Collects all benchmark results in `dir` saved since `since`, in the order they were saved.
+///
+/// Results of earlier runs, e.g. of benchmarks skipped by a filter, are left out.
+fn collect_saved(dir: &Path, since: SystemTime) -> Result<Vec<Saved>, Box<dyn Error>> {
+    let mut saved = Vec::new();
+    let mut pending = vec![dir.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        let Ok(entries) = fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries {
+            let path = entry?.path();
+            if !path.is_dir() {
+                continue;
+            }
+            let benchmark_file = path.join("benchmark.json");
+            if !path.ends_with("new") || !benchmark_file.is_file() {
+                pending.push(path);
+                continue;
+            }
+            let modified = fs::metadata(path.join("estimates.json"))?.modified()?;
+            if modified < since {
+                continue;
+            }
+            let benchmark = json::from_str(&fs::read_to_string(&benchmark_file)?)?;
+            saved.push(Saved {
+                dir: path,
+                modified,
+                benchmark,
+            });
+        }
+    }
+    saved.sort_by_key(|saved| saved.modified);
+    Ok(saved)
+}
+
+/// Converts a Criterion `estimates.json` estimate into a `cargo criterion` estimate in nanoseconds.
+fn encode_estimate(estimate: &json::Value) -> json::Value {
+    if estimate.is_null() {
+        return json::Value::Null;
+    }
+    let interval = &estimate["confidence_interval"];
+    json::json!({
+        "estimate": estimate["point_estimate"],
+        "lower_bound": interval["lower_bound"],
+        "upper_bound": interval["upper_bound"],
+        "unit": "ns",
+    })
+}
+
+/// Converts a Criterion throughput, e.g. `{"Bytes": 1024}`, into `cargo criterion` form.
+fn encode_throughput(throughput: &json::Value) -> Vec<json::Value> {
+    let Some((unit, per_iteration)) = throughput.as_object().and_then(|map| map.iter().next())
+    else {
+        return Vec::new();
+    };
+    let unit = match unit.as_str() {
+        "Bytes" => "bytes",
+        "BytesDecimal" => "bytes_decimal",
+        "Elements" => "elements",
+        "Bits" => "bits",
+        _ => return Vec::new(),
+    };
+    vec![json::json!({ "per_iteration": per_iteration, "unit": unit })]
+}
+
+/// Encodes the `benchmark-complete` message of `saved`.
+fn encode_benchmark(saved: &Saved) -> Result<json::Value, Box<dyn Error>> {
+    let estimates = saved.read("estimates.json")?;
+    let sample = saved.read("sample.json")?;
+    // Criterion reports the slope as typical value if available and falls back to the mean.
+    let typical = match &estimates["slope"] {
+        json::Value::Null => &estimates["mean"],
+        slope => slope,
+    };
+    // Criterion saves the iteration counts as floats while `cargo criterion` emits integers.
+    let iteration_count: Vec<u64> = sample["iters"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(json::Value::as_f64)
+        .map(|iters| iters as u64)
+        .collect();
+    Ok(json::json!({
+        "reason": "benchmark-complete",
+        "id": saved.id()?,
+        "report_directory": saved.report_directory(),
+        "iteration_count": iteration_count,
+        "measured_values": sample["times"],
+        "unit": "ns",
+        "throughput": encode_throughput(&saved.benchmark["throughput"]),
+        "typical": encode_estimate(typical),
+        "mean": encode_estimate(&estimates["mean"]),
+        "median": encode_estimate(&estimates["median"]),
+        "median_abs_dev": encode_estimate(&estimates["median_abs_dev"]),
+        "slope": encode_estimate(&estimates["slope"]),
+        "change": json::Value::Null,
+    }))
+}
+
+/// Returns the CPU model name of the machine, if it can be determined.
+fn cpu_model() -> Option<String> {
+    if cfg!(target_os = "linux") {
+        let cpuinfo = fs::read_to_string("/proc/cpuinfo").ok()?;
+        return cpuinfo
+            .lines()
+            .find(|line| line.starts_with("model name"))
+            .and_then(|line| line.split_once(':'))
+            .map(|(_, model)| model.trim().to_string());
+    }
+    if cfg!(target_os = "macos") {
+        let output = Command::new("sysctl")
+            .args(["-n", "machdep.cpu.brand_string"])
+            .output()
+            .ok()?;
+        return Some(String::from_utf8(output.stdout).ok()?.trim().to_string());
+    }
+    None
+}
+
+/// Encodes the `machine-info` message describing the machine and build the benchmarks ran on.
+///
+/// Consumers of `cargo criterion` messages skip it just like other non-benchmark messages.
+fn encode_machine_info() -> Result<json::Value, Box<dyn Error>> {
+    let timestamp = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+    let runtimes: Vec<String> = vms_under_test()
+        .iter()
+        .map(|vm| vm.id().to_string())
+        .collect();
+    Ok(json::json!({
+        "reason": "machine-info",
+        "timestamp": timestamp,
+        "os": env::consts::OS,
+        "arch": env::consts::ARCH,
+        "cpu": cpu_model(),
+        "threads": std::thread::available_parallelism().map(usize::from).ok(),
+        "runtimes": runtimes,
+    }))
+}
+
+/// Encodes a `runtime-info` message with the metadata of every runtime compiled into the benchmarks,
+/// including their metered configurations, in the order they are benchmarked.
+///
+/// Results are usually plotted by a build with other features, which therefore takes the labels,
+/// kinds and whether runtimes are outdated from these messages instead of from its own adapters.
+fn encode_runtime_infos() -> Vec<json::Value> {
+    vms_under_test()
+        .into_iter()
+        .chain(metered_vms_under_test())
+        .map(|vm| {
+            json::json!({
+                "reason": "runtime-info",
+                "id": vm.id(),
+                "label": vm.label(),
+                "kind": match vm.kind() {
+                    RuntimeKind::Jit => "jit",
+                    RuntimeKind::Interpreter => "interpreter",
+                },
+                "is_outdated": vm.is_outdated(),
+            })
+        })
+        .collect()
+}
+
+/// Writes all benchmark results saved since `since` as JSON lines to `path`.
+///
+/// Starts with the machine information, the metadata of the runtimes, the sizes of the
+/// benchmarked startup modules and
+/// their compiled artifacts and the hardware performance counters, followed by the
+/// `benchmark-complete` messages of every benchmark group, each group closed by its
+/// `group-complete` message.
+///
+/// Criterion saves the results of every run under `new`, also with `--save-baseline` which
+/// additionally copies them to the named baseline. Fails if no results were saved at all, e.g.
+/// with `--discard-baseline` or `--load-baseline`, instead of writing an empty file. Isolated
+/// children may write no results, e.g. if the filter excludes their pair, since the parent
+/// checks the results of all children together, see [`merge_results`].
+///
+/// The memory pass only writes the machine information, the metadata of the runtimes and the
+/// peak memory usages, since
+/// it runs no timed benchmarks.
+pub fn write_results(path: &Path, since: SystemTime) -> Result<(), Box<dyn Error>> {
+    if memory::is_enabled() {
+        return write_memory_usages(path);
+    }
+    let criterion_dir = criterion_dir();
+    let saved = collect_saved(&criterion_dir, since)?;
+    if saved.is_empty() && !isolate::is_child() {
+        return Err(format!(
+            "found no benchmark results saved by this run under {}: \
+            Criterion saves none with `--discard-baseline`, `--load-baseline` \
+            or a filter matching no benchmark",
+            criterion_dir.display()
+        )
+        .into());
+    }
+    let mut groups: Vec<(&str, Vec<&Saved>)> = Vec::new();
+    for saved in &saved {
+        let group = saved.group()?;
+        match groups.iter_mut().find(|(name, _)| *name == group) {
+            Some((_, benchmarks)) => benchmarks.push(saved),
+            None => groups.push((group, vec![saved])),
+        }
+    }
+    let mut file = BufWriter::new(fs::File::create(path)?);
+    writeln!(file, "{}", encode_machine_info()?)?;
+    for runtime_info in encode_runtime_infos() {
+        writeln!(file, "{runtime_info}")?;
+    }
+    let names: Vec<&str> = groups.iter().map(|(name, _)| *name).collect();
+    for module_info in encode_module_infos(&names) {
+        writeln!(file, "{module_info}")?;
+    }
+    for compiled_size in encode_compiled_sizes() {
+        writeln!(file, "{compiled_size}")?;
+    }
+    for perf_counts in encode_perf_counts() {
+        writeln!(file, "{perf_counts}")?;
+    }
+    for (group, benchmarks) in &groups {
+        let mut ids = Vec::new();
+        for saved in benchmarks {
+            writeln!(file, "{}", encode_benchmark(saved)?)?;
+            ids.push(saved.id()?);
+        }
+        // Criterion saves all benchmarks of a group below the same top-level directory.
+        let group_dir = benchmarks[0]
+            .dir
+            .strip_prefix(&criterion_dir)
+            .ok()
+            .and_then(|dir| dir.components().next())
+            .map(|dir| criterion_dir.join(dir))
+            .unwrap_or_else(|| criterion_dir.clone());
+        let report_directory = group_dir.join("report");
+        let group_complete = json::json!({
+            "reason": "group-complete",
+            "group_name": group,
+            "benchmarks": ids,
+            "report_directory": report_directory.display().to_string(),
+        });
+        writeln!(file, "{group_complete}")?;
+    }
+    file.flush()?;
+    eprintln!(
+        "wrote {} benchmark results of {} groups to {}",
+        saved.len(),
+        groups.len(),
+        path.display()
+    );
+    Ok(())
+}
+
+/// Writes the peak memory usages measured by the memory pass as JSON lines to `path`.
+fn write_memory_usages(path: &Path) -> Result<(), Box<dyn Error>> {
+    let usages = encode_memory_usages();
+    if usages.is_empty() && !isolate::is_child() {
+        return Err("the memory pass measured no benchmark: check the filter".into());
+    }
+    let mut file = BufWriter::new(fs::File::create(path)?);
+    writeln!(file, "{}", encode_machine_info()?)?;
+    for runtime_info in encode_runtime_infos() {
+        writeln!(file, "{runtime_info}")?;
+    }
+    for memory_usage in &usages {
+        writeln!(file, "{memory_usage}")?;
+    }
+    file.flush()?;
+    eprintln!(
+        "wrote the memory usage of {} benchmarks to {}",
+        usages.len(),
+        path.display()
+    );
+    Ok(())
+}
+
+/// Merges the results written by isolated child processes to `parts` into `path`.
+///
+/// Writes a single `machine-info` message and the `runtime-info` messages followed by all other
+/// messages of every part in order. Fails if the parts hold no benchmark results at all.
+pub fn merge_results(path: &Path, parts: &[PathBuf]) -> Result<(), Box<dyn Error>> {
+    let mut file = BufWriter::new(fs::File::create(path)?);
+    writeln!(file, "{}", encode_machine_info()?)?;
+    for runtime_info in encode_runtime_infos() {
+        writeln!(file, "{runtime_info}")?;
+    }
+    let mut benchmarks = 0;
+    for part in parts {
+        for line in fs::read_to_string(part)?.lines() {
+            let message: json::Value = json::from_str(line)?;
+            match message.get("reason").and_then(json::Value::as_str) {
+                Some("machine-info" | "runtime-info") => continue,
+                Some("benchmark-complete" | "memory-complete") => benchmarks += 1,
+                _ => {}
+            }
+            writeln!(file, "{line}")?;
+        }
+    }
+    file.flush()?;
+    if benchmarks == 0 {
+        return Err("the isolated runs executed no benchmark: check the filter".into());
+    }
+    eprintln!(
+        "merged {benchmarks} benchmark results of {} isolated runs to {}",
+        parts.len(),
+        path.display()
+    );
+    Ok(())
+}
@@ -1,7 +1,11 @@
-use benchmark_utils::{FuncType, InputEncoding, StartupTestId, Val, ValType, read_benchmark_file};
-use criterion::{Criterion, criterion_group};
+use crate::isolate::vms_under_test;
+use crate::memory::bench_function;
+use crate::report::{record_compiled_size, record_module};
+use benchmark_utils::{
+    FuncType, InputEncoding, ModuleStats, StartupTestId, Val, ValType, read_benchmark_file,
+};
+use criterion::{Criterion, Throughput, criterion_group};
 use std::time::Duration;
-use wasmi_benchmarks::vms_under_test;
 
 criterion_group!(
     name = bench_startup;
@@ -57,6 +61,7 @@ fn required_imports(id: StartupTestId) -> Vec<(&'static str, &'static str, FuncT
     let wasi = "wasi_snapshot_preview1";
     match id {
         StartupTestId::Argon2 => vec![],
+        StartupTestId::Trivial => vec![("env", "host", i_i())],
         StartupTestId::CoreMark => vec![("env", "clock_ms", func_ty(&[], &[I32]))],
         StartupTestId::Erc20 => vec![
             ("__unstable__", "seal_get_storage", iiii_i()),
@@ -152,8 +157,13 @@ fn required_imports(id: StartupTestId) -> Vec<(&'static str, &'static str, FuncT
 fn instantiate_benchmark(c: &mut Criterion, id: StartupTestId, encoding: InputEncoding) {
     let wasm = read_benchmark_file(encoding, id.into());
     let imports = required_imports(id);
-    let mut g = c.benchmark_group(format!("startup/{id}"));
-    for vm in vms_under_test() {
+    let group = format!("startup/{id}");
+    // Startup times mostly scale with the code to validate and translate: report code bytes per second.
+    let stats = ModuleStats::new(&wasm[..]);
+    record_module(&group, stats);
+    let mut g = c.benchmark_group(&group);
+    g.throughput(Throughput::Bytes(stats.code_size as u64));
+    for vm in vms_under_test(&group) {
         let Some(mut rt) = vm.setup(id.into()) else {
             continue;
         };
@@ -162,7 +172,9 @@ fn instantiate_benchmark(c: &mut Criterion, id: StartupTestId, encoding: InputEn
             rt.link_func(module, field, ty.clone(), stub);
         }
         let bench_id = vm.id().to_string();
-        g.bench_function(&bench_id, |b| {
+        let full_id = format!("startup/{id}/{bench_id}");
+        bench_function(&mut g, &bench_id, &full_id, |b| {
+            record_compiled_size(&full_id, || rt.compiled_size(&wasm[..]));
             b.iter(|| {
                 rt.instantiate(&wasm[..]);
             });
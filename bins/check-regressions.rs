@@ -0,0 +1,168 @@
+use clap::Parser;
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fs;
+use std::io::{self, BufRead};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use wasmi_benchmarks::results::{BenchId, Estimate, Measurement, format_duration_ns};
+
+/// Compares Criterion benchmark results (read as JSON from stdin) against a stored baseline.
+///
+/// Exits with a non-zero code if any benchmark regressed or if no benchmark was compared at all,
+/// e.g. because of empty input, a wrong baseline or a `--runtime` filter matching nothing.
+#[derive(Debug, Parser)]
+struct Args {
+    /// The baseline Criterion JSON output, e.g. `data/apple-m2-pro/criterion.json`.
+    #[arg(long)]
+    baseline: PathBuf,
+    /// The slowdown in percent above which a benchmark counts as regressed.
+    #[arg(long, default_value_t = 5.0)]
+    threshold: f64,
+    /// Overrides the threshold of a single test case, e.g. `execute/fibonacci-rec=10`.
+    ///
+    /// May be given repeatedly or as a comma separated list.
+    #[arg(long = "case-threshold", value_delimiter = ',', value_parser = parse_case_threshold)]
+    case_thresholds: Vec<(String, f64)>,
+    /// Only checks the Wasm runtimes whose ID starts with one of the given prefixes, e.g. `wasmi-v2`.
+    ///
+    /// May be given repeatedly or as a comma separated list.
+    #[arg(long = "runtime", value_delimiter = ',')]
+    runtimes: Vec<String>,
+    /// Also fails if any benchmark is missing from the baseline.
+    #[arg(long)]
+    fail_on_missing: bool,
+}
+
+/// Parses a `{category}/{case}={percent}` threshold override.
+fn parse_case_threshold(input: &str) -> Result<(String, f64), String> {
+    let Some((case, threshold)) = input.rsplit_once('=') else {
+        return Err(format!(
+            "expected `{{category}}/{{case}}={{percent}}` but found {input:?}"
+        ));
+    };
+    let threshold = threshold
+        .parse::<f64>()
+        .map_err(|error| format!("invalid threshold {threshold:?}: {error}"))?;
+    Ok((case.to_string(), threshold))
+}
+
+/// The outcome of comparing a single benchmark against its baseline.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+enum Verdict {
+    /// Slower by more than the threshold with non-overlapping confidence intervals.
+    Regressed,
+    /// Faster by more than the threshold with non-overlapping confidence intervals.
+    Improved,
+    /// Neither regressed nor improved significantly.
+    Unchanged,
+}
+
+impl Verdict {
+    /// Compares `new` against `old` given the `threshold` in percent.
+    fn of(old: Estimate, new: Estimate, threshold: f64) -> Self {
+        let change = change_percent(old, new);
+        if change > threshold && new.lower_bound > old.upper_bound {
+            return Self::Regressed;
+        }
+        if change < -threshold && new.upper_bound < old.lower_bound {
+            return Self::Improved;
+        }
+        Self::Unchanged
+    }
+
+    /// Returns the label of `self` as printed in the report.
+    fn label(self) -> &'static str {
+        match self {
+            Self::Regressed => "REGRESSED",
+            Self::Improved => "improved",
+            Self::Unchanged => "unchanged",
+        }
+    }
+}
+
+/// Returns the change from `old` to `new` in percent, positive meaning slower.
+fn change_percent(old: Estimate, new: Estimate) -> f64 {
+    (new.estimate / old.estimate - 1.0) * 100.0
+}
+
+/// Decodes all benchmark results of the Criterion JSON output in `reader`.
+fn decode_results(
+    source: &Path,
+    reader: impl BufRead,
+) -> Result<BTreeMap<BenchId, Estimate>, Box<dyn Error>> {
+    let mut results = BTreeMap::new();
+    for (index, line) in reader.lines().enumerate() {
+        let measurement = Measurement::decode(&line?)
+            .map_err(|error| format!("{}:{}: {error}", source.display(), index + 1))?;
+        if let Some(measurement) = measurement {
+            results.insert(measurement.id, measurement.typical);
+        }
+    }
+    Ok(results)
+}
+
+fn main() -> Result<ExitCode, Box<dyn Error>> {
+    let args = Args::parse();
+    let case_thresholds: BTreeMap<_, _> = args.case_thresholds.into_iter().collect();
+    let baseline = decode_results(
+        &args.baseline,
+        io::BufReader::new(fs::File::open(&args.baseline)?),
+    )?;
+    let current = decode_results(Path::new("<stdin>"), io::stdin().lock())?;
+    let mut report = Vec::new();
+    let mut missing = 0;
+    for (id, new) in &current {
+        if !args.runtimes.is_empty()
+            && !args
+                .runtimes
+                .iter()
+                .any(|prefix| id.runtime.starts_with(prefix))
+        {
+            continue;
+        }
+        let Some(old) = baseline.get(id) else {
+            missing += 1;
+            continue;
+        };
+        let threshold = case_thresholds
+            .get(&id.group())
+            .copied()
+            .unwrap_or(args.threshold);
+        let verdict = Verdict::of(*old, *new, threshold);
+        report.push((verdict, id, *old, *new, threshold));
+    }
+    // Regressions first, then improvements, each group in benchmark order.
+    report.sort_by(|lhs, rhs| (lhs.0, lhs.1).cmp(&(rhs.0, rhs.1)));
+    for (verdict, id, old, new, threshold) in &report {
+        if *verdict == Verdict::Unchanged {
+            continue;
+        }
+        println!(
+            "{:<10} {id}: {} -> {} ({:+.02}%, threshold {threshold}%)",
+            verdict.label(),
+            format_duration_ns(old.estimate),
+            format_duration_ns(new.estimate),
+            change_percent(*old, *new),
+        );
+    }
+    let count = |verdict| report.iter().filter(|entry| entry.0 == verdict).count();
+    let regressed = count(Verdict::Regressed);
+    println!(
+        "\n{} benchmarks compared: {regressed} regressed, {} improved, {} unchanged",
+        report.len(),
+        count(Verdict::Improved),
+        count(Verdict::Unchanged),
+    );
+    if missing != 0 {
+        println!("{missing} benchmarks skipped: not found in the baseline");
+    }
+    if report.is_empty() {
+        eprintln!("error: no benchmarks compared, check the input, the baseline and `--runtime`");
+        return Ok(ExitCode::FAILURE);
+    }
+    if regressed != 0 || (args.fail_on_missing && missing != 0) {
+        return Ok(ExitCode::FAILURE);
+    }
+    Ok(ExitCode::SUCCESS)
+}
@@ -1,10 +1,61 @@
 use benchmark_utils::{
-    ExecuteTestId, FuncType, InputEncoding, StartupTestId, TestId, Val, ValType,
+    ExecuteTestId, FuncType, InputEncoding, Runtime, StartupTestId, TestId, Val, ValType,
     read_benchmark_file,
 };
+use clap::Parser;
 use std::collections::BTreeMap;
+use std::env;
+use std::error::Error;
+use std::fs;
+use std::io::{BufRead as _, BufReader};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
 use wasmi_benchmarks::vms_under_test;
 
+/// Runs CoreMark on all Wasm runtimes and reports their scores.
+#[derive(Debug, Parser)]
+struct Args {
+    /// The number of measured CoreMark runs per runtime.
+    ///
+    /// The reported score of a runtime is the median of its runs.
+    #[arg(long, default_value_t = 1, value_parser = clap::value_parser!(u32).range(1..))]
+    repeat: u32,
+    /// The number of unmeasured CoreMark runs per runtime before the measured runs.
+    #[arg(long, default_value_t = 0)]
+    warmup: u32,
+    /// Only runs the runtimes with the given IDs or ID prefixes, e.g. `wasmi` or `wasm3.eager`.
+    ///
+    /// May be given repeatedly or as a comma separated list.
+    #[arg(long = "runtime", value_delimiter = ',')]
+    runtimes: Vec<String>,
+    /// Writes the scores into `coremark.json` and `coremark.csv` within this directory,
+    /// e.g. `data/apple-m2-pro`.
+    #[arg(long)]
+    out_dir: Option<PathBuf>,
+    /// The number of seconds after which the CoreMark runs of a runtime are aborted as failed.
+    #[arg(long, default_value_t = 600)]
+    timeout: u64,
+    /// Runs CoreMark on the runtime with this ID within the current process.
+    ///
+    /// Used to isolate every runtime in its own child process, so that a runtime that
+    /// crashes or hangs does not take down the CoreMark runs of all other runtimes.
+    #[arg(long, hide = true)]
+    child: Option<String>,
+}
+
+impl Args {
+    /// Returns `true` if the runtime with `id` passes the runtime filter.
+    fn keeps(&self, id: &str) -> bool {
+        self.runtimes.is_empty()
+            || self
+                .runtimes
+                .iter()
+                .any(|filter| id.starts_with(filter.as_str()))
+    }
+}
+
 /// Used to query elapsed time since last time this has been called. Used for Coremark benchmark.
 fn elapsed_ms() -> u32 {
     use std::time::Instant;
@@ -21,36 +72,236 @@ fn clock_ms(_params: &[Val], results: &mut [Val]) {
     results[0] = Val::I32(elapsed_ms() as i32);
 }
 
-fn main() {
+/// The statistics of the CoreMark scores of all measured runs of a single runtime.
+#[derive(Debug, Copy, Clone)]
+struct Stats {
+    median: f32,
+    min: f32,
+    max: f32,
+    stddev: f32,
+}
+
+impl Stats {
+    /// Computes the [`Stats`] of the non-empty `scores`.
+    fn new(mut scores: Vec<f32>) -> Self {
+        scores.sort_by(f32::total_cmp);
+        let len = scores.len();
+        let median = match len % 2 {
+            0 => (scores[len / 2 - 1] + scores[len / 2]) / 2.0,
+            _ => scores[len / 2],
+        };
+        let mean = scores.iter().sum::<f32>() / len as f32;
+        let variance = scores
+            .iter()
+            .map(|score| (score - mean).powi(2))
+            .sum::<f32>()
+            / len as f32;
+        Self {
+            median,
+            min: scores[0],
+            max: scores[len - 1],
+            stddev: variance.sqrt(),
+        }
+    }
+}
+
+/// Runs CoreMark `warmup + repeat` times on `vm` and returns the scores of the measured runs.
+///
+/// Every run uses a fresh instance so that no run observes the state left behind by another.
+/// Returns `None` if `vm` cannot run CoreMark.
+fn run_coremark(vm: &dyn Runtime, wasm: &[u8], args: &Args) -> Option<Vec<f32>> {
+    let mut rt = vm.setup(ExecuteTestId::CoreMark.into())?;
+    rt.link_func(
+        "env",
+        "clock_ms",
+        FuncType::new([], [ValType::I32]),
+        clock_ms,
+    );
+    let mut scores = Vec::new();
+    for run in 0..args.warmup + args.repeat {
+        let mut instance = rt.instantiate(wasm);
+        let mut results = [Val::F32(0.0)];
+        instance.call("run", &[], &mut results[..]).unwrap();
+        let score = results[0].unwrap_f32();
+        if run < args.warmup {
+            println!("\twarm-up score = {score}");
+            continue;
+        }
+        println!("\tscore = {score}");
+        scores.push(score);
+    }
+    Some(scores)
+}
+
+/// The prefix of the line with which a child process reports its scores to its parent.
+const SCORES_PREFIX: &str = "scores: ";
+
+/// The outcome of the CoreMark runs of a single runtime in a child process.
+enum Outcome {
+    /// The scores of the measured runs.
+    Scores(Vec<f32>),
+    /// The runtime cannot run CoreMark.
+    Unsupported,
+    /// The child process crashed, timed out or reported no scores.
+    Failed(String),
+}
+
+/// Runs CoreMark on the runtime with `id` in a child process and returns its [`Outcome`].
+///
+/// The child process is the current executable invoked with `--child {id}` and forwards its
+/// progress to the output of the current process. It is killed once `args.timeout` elapsed.
+fn run_child(id: &str, args: &Args) -> Result<Outcome, Box<dyn Error>> {
+    let mut child = Command::new(env::current_exe()?)
+        .args(["--child", id])
+        .args(["--repeat", &args.repeat.to_string()])
+        .args(["--warmup", &args.warmup.to_string()])
+        .stdout(Stdio::piped())
+        .spawn()?;
+    let stdout = child
+        .stdout
+        .take()
+        .expect("stdout of child process is piped");
+    let reader = thread::spawn(move || {
+        let mut scores = None;
+        for line in BufReader::new(stdout).lines().map_while(Result::ok) {
+            match line.strip_prefix(SCORES_PREFIX) {
+                Some(json) => scores = Some(json.to_string()),
+                None => println!("{line}"),
+            }
+        }
+        scores
+    });
+    let timeout = Duration::from_secs(args.timeout);
+    let started = Instant::now();
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break Some(status);
+        }
+        if started.elapsed() >= timeout {
+            child.kill()?;
+            child.wait()?;
+            break None;
+        }
+        thread::sleep(Duration::from_millis(50));
+    };
+    let scores = reader.join().unwrap_or(None);
+    let outcome = match (status, scores) {
+        (None, _) => Outcome::Failed(format!("timed out after {}s", args.timeout)),
+        (Some(status), _) if !status.success() => Outcome::Failed(format!("crashed: {status}")),
+        (Some(_), None) => Outcome::Failed(String::from("reported no scores")),
+        (Some(_), Some(scores)) => match serde_json::from_str::<Option<Vec<f32>>>(&scores) {
+            Ok(Some(scores)) if !scores.is_empty() => Outcome::Scores(scores),
+            Ok(Some(_)) => Outcome::Failed(String::from("reported no scores")),
+            Ok(None) => Outcome::Unsupported,
+            Err(error) => Outcome::Failed(format!("reported malformed scores: {error}")),
+        },
+    };
+    Ok(outcome)
+}
+
+/// Runs CoreMark on the runtime with `id` and reports its scores to the parent process.
+///
+/// See [`run_child`] for the counterpart in the parent process.
+fn main_child(id: &str, args: &Args) -> Result<(), Box<dyn Error>> {
     let coremark_wasm = read_benchmark_file(
         InputEncoding::Wasm,
         TestId::Startup(StartupTestId::CoreMark),
     );
+    let Some(vm) = vms_under_test().into_iter().find(|vm| vm.id() == id) else {
+        return Err(format!("unknown runtime: {id}").into());
+    };
+    let scores = run_coremark(&*vm, &coremark_wasm[..], args);
+    println!("{SCORES_PREFIX}{}", serde_json::to_string(&scores)?);
+    Ok(())
+}
+
+/// Returns the name of the runtime with `id` as used by the `coremark.csv` files.
+///
+/// The CSV files name runtimes without their default configuration, e.g. `wasmi v2` for
+/// `wasmi-v2.eager.checked` or `wasm3` for `wasm3.eager`, which `plot --coremark` resolves back.
+fn csv_name(id: &str) -> String {
+    let runtime = [".eager.checked", ".eager", ".interpreter"]
+        .iter()
+        .find_map(|config| id.strip_suffix(config))
+        .unwrap_or(id);
+    runtime.replacen("wasmi-v", "wasmi v", 1)
+}
+
+/// Writes the median `scores` into `coremark.json` and `coremark.csv` within `out_dir`.
+///
+/// Follows the format of the files stored under `data/*`: the JSON file maps runtime IDs to
+/// scores and the CSV file lists the rounded scores from best to worst.
+fn write_scores(out_dir: &Path, scores: &BTreeMap<String, f32>) -> Result<(), Box<dyn Error>> {
+    fs::create_dir_all(out_dir)?;
+    let json_path = out_dir.join("coremark.json");
+    let json = serde_json::to_value(scores)?;
+    fs::write(&json_path, format!("{json:#}\n"))?;
+    let mut ranking: Vec<(&String, &f32)> = scores.iter().collect();
+    ranking.sort_by(|(_, lhs), (_, rhs)| rhs.total_cmp(lhs));
+    let mut csv = String::from("runtime,score\n");
+    for (id, score) in ranking {
+        csv.push_str(&format!("{},{}\n", csv_name(id), score.round()));
+    }
+    let csv_path = out_dir.join("coremark.csv");
+    fs::write(&csv_path, csv)?;
+    println!(
+        "Wrote scores to {} and {}",
+        json_path.display(),
+        csv_path.display()
+    );
+    Ok(())
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let args = Args::parse();
+    if let Some(id) = &args.child {
+        return main_child(id, &args);
+    }
     let mut scores = <BTreeMap<String, f32>>::new();
+    let mut failures = <BTreeMap<String, String>>::new();
     for vm in vms_under_test() {
-        let Some(mut rt) = vm.setup(ExecuteTestId::CoreMark.into()) else {
-            continue;
-        };
         let id = vm.id();
+        if !args.keeps(id) {
+            continue;
+        }
         println!(
             "\
             Running Coremark 1.0\n\
             \tusing {id} ...\
         "
         );
-        rt.link_func(
-            "env",
-            "clock_ms",
-            FuncType::new([], [ValType::I32]),
-            clock_ms,
-        );
-        let mut instance = rt.instantiate(&coremark_wasm[..]);
-        let mut results = [Val::F32(0.0)];
-        instance.call("run", &[], &mut results[..]).unwrap();
-        let score = results[0].unwrap_f32();
-        scores.insert(id.into(), score);
-        println!("\tscore = {score}\n");
+        let runs = match run_child(id, &args)? {
+            Outcome::Scores(runs) => runs,
+            Outcome::Unsupported => {
+                println!("\tunsupported\n");
+                continue;
+            }
+            Outcome::Failed(reason) => {
+                println!("\tfailed: {reason}\n");
+                failures.insert(id.into(), reason);
+                continue;
+            }
+        };
+        let Stats {
+            median,
+            min,
+            max,
+            stddev,
+        } = Stats::new(runs);
+        scores.insert(id.into(), median);
+        if args.repeat > 1 {
+            println!("\tmedian = {median}, min = {min}, max = {max}, stddev = {stddev}");
+        }
+        println!();
     }
-    let json = serde_json::to_value(&scores).unwrap();
+    let json = serde_json::to_value(&scores)?;
     println!("Scores Summary (JSON):\n{json:#}\n");
+    if !failures.is_empty() {
+        let json = serde_json::to_value(&failures)?;
+        println!("Failures Summary (JSON):\n{json:#}\n");
+    }
+    if let Some(out_dir) = &args.out_dir {
+        write_scores(out_dir, &scores)?;
+    }
+    Ok(())
 }
@@ -0,0 +1,220 @@
+//! Renders CoreMark scores as printed by the `coremark` binary.
+
+use super::output::{Format, Output};
+use super::{Filters, Style, VmAndConfig, draw_legend, plot_title};
+use plotters::coord::Shift;
+use plotters::prelude::*;
+use plotters::style::text_anchor::{HPos, Pos, VPos};
+use serde_json as json;
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+use wasmi_benchmarks::results::DecodeError;
+
+/// The CoreMark score of a single Wasm runtime.
+#[derive(Debug, Clone)]
+pub struct Score {
+    /// The Wasm runtime if its name resolves to the ID of a known runtime.
+    pub vm: Option<VmAndConfig>,
+    /// The name of the Wasm runtime as found in the input.
+    pub name: String,
+    /// The CoreMark score, higher is better.
+    pub score: f64,
+}
+
+impl Score {
+    /// Creates a new [`Score`] resolving `name` to a known Wasm runtime if possible.
+    ///
+    /// The hand-curated `coremark.csv` files name runtimes loosely, e.g. `Wasmi v2` for
+    /// `wasmi-v2.eager.checked` or `wasm3` for `wasm3.eager`, so names that are no runtime ID
+    /// are normalized and resolved to the runtime's default configuration.
+    fn new(name: &str, score: f64) -> Self {
+        let normalized = name.trim().to_lowercase().replace([' ', '_'], "-");
+        let vm = ["", ".eager.checked", ".eager", ".interpreter"]
+            .iter()
+            .find_map(|config| VmAndConfig::lookup(&format!("{normalized}{config}")));
+        Self {
+            vm,
+            name: name.trim().to_string(),
+            score,
+        }
+    }
+
+    /// Returns the label of the Wasm runtime.
+    pub fn label(&self) -> &str {
+        match &self.vm {
+            Some(vm) => vm.label(),
+            None => &self.name,
+        }
+    }
+
+    /// Returns the color of the score's bar, see [`VmAndConfig::color`].
+    fn color(&self, style: Style) -> RGBColor {
+        match &self.vm {
+            Some(vm) => vm.color(style.highlights),
+            None => VmAndConfig::TEAL,
+        }
+    }
+}
+
+/// Reads the CoreMark scores from `path`.
+///
+/// Accepts both the JSON summary printed by the `coremark` binary, mapping runtime IDs to scores,
+/// and CSV files with a `runtime` and a `score` column as stored under `data/*/coremark.csv`.
+pub fn read_scores(path: &Path) -> Result<Vec<Score>, Box<dyn Error>> {
+    let contents = fs::read_to_string(path)?;
+    if path.extension().is_some_and(|extension| extension == "csv") {
+        return read_csv(&contents);
+    }
+    let scores: BTreeMap<String, f64> = json::from_str(&contents)?;
+    Ok(scores
+        .iter()
+        .map(|(name, score)| Score::new(name, *score))
+        .collect())
+}
+
+/// Reads the CoreMark scores from the `runtime,score` CSV `contents`.
+fn read_csv(contents: &str) -> Result<Vec<Score>, Box<dyn Error>> {
+    let mut lines = contents.lines().filter(|line| !line.trim().is_empty());
+    let header: Vec<&str> = lines
+        .next()
+        .unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+        .collect();
+    let (Some(runtime), Some(score)) = (
+        header.iter().position(|&column| column == "runtime"),
+        header.iter().position(|&column| column == "score"),
+    ) else {
+        return Err(DecodeError::from("CSV must contain `runtime` and `score` columns").into());
+    };
+    lines
+        .enumerate()
+        .map(|(index, line)| {
+            let columns: Vec<&str> = line.split(',').collect();
+            let (Some(name), Some(value)) = (columns.get(runtime), columns.get(score)) else {
+                return Err(DecodeError::from(format!("line {}: malformed row", index + 2)).into());
+            };
+            Ok(Score::new(name, value.trim().parse()?))
+        })
+        .collect()
+}
+
+/// Renders the CoreMark `scores` into `coremark.svg` within the output directory.
+///
+/// Highest score on top, colored and filtered just like the Criterion results.
+pub fn plot_coremark(
+    ext_title: Option<&str>,
+    style: Style,
+    output: &Output,
+    filters: &Filters,
+    scores: &[Score],
+) -> Result<Option<String>, Box<dyn Error>> {
+    let mut data: Vec<&Score> = scores
+        .iter()
+        .filter(|score| score.vm.is_none_or(|vm| filters.keeps(vm)))
+        .collect();
+    if data.is_empty() {
+        return Ok(None);
+    }
+    // Lowest score first: the first bar is drawn at the bottom.
+    data.sort_by(|lhs, rhs| lhs.score.total_cmp(&rhs.score));
+    let path = output.plot_path("coremark")?;
+    let title = plot_title(ext_title, "CoreMark");
+    let size = output.bar_chart_size(data.len());
+    match output.format {
+        Format::Svg => {
+            let root = SVGBackend::new(&path, size).into_drawing_area();
+            draw_coremark(root, &title, style, output, &data)?;
+        }
+        Format::Png => {
+            let root = BitMapBackend::new(&path, size).into_drawing_area();
+            draw_coremark(root, &title, style, output, &data)?;
+        }
+    }
+    Ok(Some(path))
+}
+
+/// Draws the CoreMark scores `data`, sorted from lowest to highest, onto `root`.
+fn draw_coremark<DB>(
+    root: DrawingArea<DB, Shift>,
+    title: &str,
+    style: Style,
+    output: &Output,
+    data: &[&Score],
+) -> Result<(), Box<dyn Error>>
+where
+    DB: DrawingBackend,
+    DB::ErrorType: 'static,
+{
+    let max = data.last().map(|score| score.score).unwrap_or(1.0);
+    root.fill(output.theme.background())?;
+    let root = root.margin(5, 5, 5, 5).titled(
+        title,
+        output
+            .text("monospace", 45)
+            .pos(Pos::new(HPos::Center, VPos::Center)),
+    )?;
+    let mut chart = ChartBuilder::on(&root)
+        .x_label_area_size(output.scaled(75))
+        .y_label_area_size(output.scaled(400))
+        .margin_right(output.scaled(200))
+        .margin_top(output.scaled(25))
+        // Leaves room for the value labels drawn past the end of the longest bar.
+        .build_cartesian_2d(0.0..max * 1.08, (0usize..data.len() - 1).into_segmented())?;
+    let y_label_formatter = |coord: &SegmentValue<usize>| match coord {
+        SegmentValue::CenterOf(n) => data[*n].label().to_string(),
+        SegmentValue::Exact(_n) => unreachable!(),
+        SegmentValue::Last => unreachable!(),
+    };
+    let foreground = output.theme.foreground();
+    chart
+        .configure_mesh()
+        .disable_y_mesh()
+        .x_max_light_lines(1)
+        .axis_style(foreground)
+        .bold_line_style(foreground.mix(0.15))
+        .y_desc("")
+        .x_desc("Score (higher is better)")
+        .y_label_formatter(&y_label_formatter)
+        .x_label_formatter(&|score| format!("{score:.0}"))
+        .x_label_style(output.text("sans-serif", 20))
+        .y_label_style(output.text("sans-serif", 30))
+        .axis_desc_style(output.text("sans-serif", 35))
+        .x_labels(5)
+        .y_labels(data.len())
+        .draw()?;
+    chart.draw_series(
+        Histogram::horizontal(&chart)
+            .style_func(|x, _bar_height| match x {
+                SegmentValue::Exact(n) => data[*n].color(style).filled(),
+                SegmentValue::CenterOf(_n) => unreachable!(),
+                SegmentValue::Last => unreachable!(),
+            })
+            .margin(output.scaled(15))
+            .baseline(0.0)
+            .data(
+                data.iter()
+                    .enumerate()
+                    .map(|(index, score)| (index, score.score)),
+            ),
+    )?;
+    let label_offset = (output.scaled(10) as i32, output.scaled(2) as i32);
+    chart.draw_series(data.iter().enumerate().map(|(index, score)| {
+        // Offset by a fixed pixel amount from the bar's end, see `draw_chart`.
+        EmptyElement::at((score.score, SegmentValue::CenterOf(index)))
+            + Text::new(
+                format!("{:.0}", score.score),
+                label_offset,
+                output
+                    .text("monospace", 22)
+                    .pos(Pos::new(HPos::Left, VPos::Center)),
+            )
+    }))?;
+    // The lowest scores with the shortest bars are at the bottom.
+    draw_legend(&mut chart, style, output, SeriesLabelPosition::LowerRight)?;
+    // To avoid the IO failure being ignored silently, we manually call the present function
+    root.present()?;
+    Ok(())
+}
@@ -0,0 +1,286 @@
+//! Summarizes all test cases of a category into a single geomean plot per category.
+
+use super::output::Output;
+use super::select::Glob;
+use super::{
+    BenchCategory, BenchEntry, BenchGroup, Filters, FromStrError, Style, Time, Unit, VmAndConfig,
+    plot_title, render_plot,
+};
+use std::collections::{BTreeMap, BTreeSet};
+use std::error::Error;
+use std::str::FromStr;
+
+/// The runtime every other runtime is normalized to per test case.
+#[derive(Debug, Clone, Default)]
+pub enum Baseline {
+    /// A theoretical optimal runtime that is the fastest measured runtime of each test case.
+    #[default]
+    Optimal,
+    /// The runtime with the given ID, e.g. `wasmi-v2.eager.checked`.
+    Runtime(String),
+}
+
+impl FromStr for Baseline {
+    type Err = FromStrError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        match input.trim() {
+            "" => Err(FromStrError::from("invalid Baseline: empty runtime ID")),
+            "optimal" => Ok(Self::Optimal),
+            id => Ok(Self::Runtime(id.to_string())),
+        }
+    }
+}
+
+/// How runtimes that did not run every test case are averaged.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default, clap::ValueEnum)]
+pub enum Coverage {
+    /// Average only the test cases that every plotted runtime ran.
+    #[default]
+    Common,
+    /// Average every runtime over the test cases it ran and annotate partial coverage.
+    Separate,
+    /// Count test cases a runtime did not run as if it were the slowest runtime of the case.
+    Impute,
+}
+
+/// The options of the geomean plots.
+#[derive(Debug, Clone, Default)]
+pub struct Options {
+    /// The runtime all others are normalized to.
+    pub baseline: Baseline,
+    /// The weights of the test cases matched by the globs: the first match applies.
+    ///
+    /// Test cases without a matching glob are weighted `1.0`.
+    pub weights: Vec<(Glob, f64)>,
+    /// How runtimes that did not run every test case are averaged.
+    pub coverage: Coverage,
+}
+
+impl Options {
+    /// Returns the weight of the test case `name` of `category`.
+    fn weight(&self, category: BenchCategory, name: &str) -> f64 {
+        let id = format!("{category}/{name}");
+        self.weights
+            .iter()
+            .find(|(glob, _)| glob.matches(name) || glob.matches(&id))
+            .map(|(_, weight)| *weight)
+            .unwrap_or(1.0)
+    }
+}
+
+/// Parses a `{case}={weight}` geomean weight, where `{case}` is a glob, e.g. `execute/fib-*=0.5`.
+pub fn parse_weight(input: &str) -> Result<(Glob, f64), String> {
+    let Some((case, weight)) = input.rsplit_once('=') else {
+        return Err(format!(
+            "expected `{{case}}={{weight}}` but found {input:?}"
+        ));
+    };
+    let weight = weight
+        .parse::<f64>()
+        .map_err(|error| format!("invalid weight {weight:?}: {error}"))?;
+    if !(weight.is_finite() && weight >= 0.0) {
+        return Err(format!("invalid weight {weight}: must not be negative"));
+    }
+    let case = case.parse::<Glob>().map_err(|error| error.to_string())?;
+    Ok((case, weight))
+}
+
+/// The measured times of all benchmark groups of a single [`BenchCategory`].
+///
+/// Collected while decoding so the geomean plot of the category can be rendered
+/// once all its groups have been seen.
+#[derive(Debug, Default)]
+pub struct GeomeanData {
+    /// One entry per test case: its name and the times of the runtimes that
+    /// passed the [`Filters`], in nanoseconds.
+    cases: Vec<(String, BTreeMap<VmAndConfig, f64>)>,
+}
+
+impl GeomeanData {
+    /// Records the filtered results of `bench_group` as another test case.
+    pub fn push_group(&mut self, filters: &Filters, bench_group: &BenchGroup) {
+        let times = bench_group
+            .entries(filters)
+            .into_iter()
+            .map(|entry| (entry.vm, entry.time))
+            .collect();
+        self.cases.push((bench_group.name.clone(), times));
+    }
+
+    /// Returns the name and the times of the runtimes of every test case.
+    pub fn cases(&self) -> &[(String, BTreeMap<VmAndConfig, f64>)] {
+        &self.cases
+    }
+
+    /// Returns the runtimes that appear in at least one test case.
+    pub fn runtimes(&self) -> BTreeSet<VmAndConfig> {
+        self.cases
+            .iter()
+            .flat_map(|(_name, times)| times.keys().copied())
+            .collect()
+    }
+}
+
+/// A test case averaged into the geomean.
+struct Case<'a> {
+    /// The weight of the test case.
+    weight: f64,
+    /// The time all runtimes are normalized to in nanoseconds.
+    baseline: f64,
+    /// The times of the runtimes that ran the test case in nanoseconds.
+    times: &'a BTreeMap<VmAndConfig, f64>,
+}
+
+/// Renders the geomean plot of `category` into `geomean-{category}.svg` within the output
+/// directory.
+///
+/// The geomean summarizes an entire category instead of being one of its test
+/// cases, so it is put next to the `{category}` folders instead of into them.
+///
+/// Every runtime is plotted as the weighted geometric mean of its per-test-case ratios
+/// `time / baseline`. By default the baseline is a theoretical optimal runtime that picks
+/// the fastest measured runtime for each test case individually. The geometric mean is the
+/// correct average for such normalized ratios: it is symmetric under being twice as fast or
+/// twice as slow and independent of the runtime the ratios are normalized to.
+///
+/// By default only the test cases that _every_ plotted runtime ran are averaged, so all
+/// bars cover the same set of test cases and stay comparable, see [`Coverage`] for the
+/// alternatives. Note that this is evaluated after `filter` has been applied: a test case
+/// that is missing only runtimes that `filter` excludes anyway still contributes to the
+/// geomean. Test cases the reference runtime did not run cannot be normalized and are
+/// always excluded.
+///
+/// The geomean is always plotted as a relative time, since averaging absolute
+/// times across differently sized test cases is meaningless.
+pub fn plot_geomean(
+    ext_title: Option<&str>,
+    style: Style,
+    output: &Output,
+    options: &Options,
+    category: BenchCategory,
+    geomean_data: &GeomeanData,
+) -> Result<Option<String>, Box<dyn Error>> {
+    let runtimes = geomean_data.runtimes();
+    if runtimes.is_empty() {
+        // No runtime of the selected kind ran at all: nothing to plot.
+        return Ok(None);
+    }
+    let reference = match &options.baseline {
+        Baseline::Optimal => None,
+        Baseline::Runtime(id) => match runtimes.iter().find(|vm| vm.id() == id) {
+            Some(vm) => Some(*vm),
+            None => {
+                eprintln!(
+                    "{category}/geomean: reference runtime {id:?} not found: skipping geomean plot"
+                );
+                return Ok(None);
+            }
+        },
+    };
+    let mut cases = Vec::new();
+    for (name, times) in &geomean_data.cases {
+        let weight = options.weight(category, name);
+        if weight == 0.0 {
+            continue;
+        }
+        let baseline = match reference {
+            None => times.values().copied().min_by(f64::total_cmp),
+            Some(reference) => times.get(&reference).copied(),
+        };
+        let Some(baseline) = baseline else {
+            let runtime = reference.map_or("any runtime", |reference| reference.label());
+            eprintln!("{category}/geomean: excluding test case {name:?}: not run by {runtime}");
+            continue;
+        };
+        let missing: Vec<_> = runtimes
+            .iter()
+            .filter(|vm| !times.contains_key(vm))
+            .map(|vm| vm.label())
+            .collect();
+        if !missing.is_empty() && options.coverage == Coverage::Common {
+            eprintln!(
+                "{category}/geomean: excluding test case {name:?}: not run by {}",
+                missing.join(", ")
+            );
+            continue;
+        }
+        cases.push(Case {
+            weight,
+            baseline,
+            times,
+        });
+    }
+    if cases.is_empty() {
+        eprintln!("{category}/geomean: no test case to average: skipping geomean plot");
+        return Ok(None);
+    }
+    eprintln!(
+        "{category}/geomean: averaging {} runtimes over {} of {} test cases",
+        runtimes.len(),
+        cases.len(),
+        geomean_data.cases.len(),
+    );
+    // Sum the weighted logarithms of the ratios per runtime, so that exponentiating
+    // their weighted mean yields the weighted geometric mean of the ratios themselves.
+    let mut data = Vec::new();
+    for vm in runtimes {
+        let (mut sum_of_logs, mut sum_of_weights, mut covered) = (0.0, 0.0, 0);
+        for case in &cases {
+            let ratio = match case.times.get(&vm) {
+                Some(time) => {
+                    covered += 1;
+                    time / case.baseline
+                }
+                None if options.coverage == Coverage::Impute => {
+                    let slowest = case.times.values().copied().max_by(f64::total_cmp);
+                    slowest.unwrap_or(case.baseline) / case.baseline
+                }
+                None => continue,
+            };
+            sum_of_logs += case.weight * ratio.ln();
+            sum_of_weights += case.weight;
+        }
+        if covered == 0 {
+            eprintln!(
+                "{category}/geomean: excluding {}: no test case run",
+                vm.label()
+            );
+            continue;
+        }
+        if covered < cases.len() {
+            eprintln!(
+                "{category}/geomean: {} ran only {covered} of {} test cases",
+                vm.label(),
+                cases.len(),
+            );
+        }
+        data.push(BenchEntry {
+            vm,
+            time: (sum_of_logs / sum_of_weights).exp(),
+            unit: Unit::Nanoseconds,
+            coverage: (covered < cases.len()).then_some((covered, cases.len())),
+            // Rates of differently sized test cases cannot be averaged.
+            throughput: None,
+        });
+    }
+    let kind = match reference {
+        None => String::from("Relative Time vs. optimal runtime"),
+        Some(reference) => format!("Time vs. {}", reference.label()),
+    };
+    let path = output.plot_path(&format!("geomean-{category}"))?;
+    render_plot(
+        &plot_title(ext_title, &format!("{category}/geomean")),
+        &path,
+        // The geomean is always plotted as a relative time.
+        Style {
+            time: Time::Relative,
+            ..style
+        },
+        output,
+        &kind,
+        1.0,
+        data,
+    )?;
+    Ok(Some(path))
+}
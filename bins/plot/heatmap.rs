@@ -0,0 +1,336 @@
+//! Renders the times of all runtimes and test cases of a category as a single heatmap.
+
+use super::geomean::{Baseline, GeomeanData};
+use super::output::{Format, Output};
+use super::{BenchCategory, Style, VmAndConfig, plot_title};
+use plotters::coord::Shift;
+use plotters::prelude::*;
+use plotters::style::text_anchor::{HPos, Pos, VPos};
+use std::collections::BTreeMap;
+use std::error::Error;
+
+/// The color of cells as fast as the baseline, or of the fastest cells for [`Baseline::Optimal`].
+const FAST: RGBColor = RGBColor(26, 152, 80);
+/// The color of cells in between fast and slow.
+const NEUTRAL: RGBColor = RGBColor(254, 224, 139);
+/// The color of the slowest cells.
+const SLOW: RGBColor = RGBColor(215, 48, 39);
+/// The color of cells of unsupported test cases.
+const UNSUPPORTED: RGBColor = RGBColor(189, 189, 189);
+
+/// Returns the marker of unsupported test cases in `format`.
+///
+/// The fonts available to rasterize PNG images usually lack emoji, so those use a plain cross.
+fn unsupported_marker(format: Format) -> &'static str {
+    match format {
+        Format::Svg => "❌",
+        Format::Png => "✗",
+    }
+}
+
+/// The number of doublings in time from one color of the scale to the next.
+///
+/// Cells that are `2^3 = 8` times slower than the fastest are [`NEUTRAL`], another factor
+/// of `8` makes them [`SLOW`]. Cells beyond the scale are clamped to its ends.
+const DOUBLINGS_PER_COLOR: f64 = 3.0;
+
+/// A test case column of the heatmap.
+struct Column<'a> {
+    /// The name of the test case.
+    name: &'a str,
+    /// The time all runtimes are normalized to in nanoseconds.
+    baseline: f64,
+    /// The times of the runtimes that ran the test case in nanoseconds.
+    times: &'a BTreeMap<VmAndConfig, f64>,
+}
+
+impl Column<'_> {
+    /// Returns the time of `vm` relative to the baseline, or `None` if it did not run the test case.
+    fn ratio(&self, vm: VmAndConfig) -> Option<f64> {
+        self.times.get(&vm).map(|time| time / self.baseline)
+    }
+}
+
+/// Returns the color of a cell with the relative time `ratio`.
+///
+/// Interpolates from [`FAST`] over [`NEUTRAL`] to [`SLOW`] in logarithmic steps. For a
+/// reference runtime as baseline, [`NEUTRAL`] is as fast as the reference runtime, so
+/// cells faster than the reference runtime are greener.
+fn cell_color(ratio: f64, baseline: &Baseline) -> RGBColor {
+    let offset = match baseline {
+        Baseline::Optimal => -1.0,
+        Baseline::Runtime(_) => 0.0,
+    };
+    let t = (ratio.log2() / DOUBLINGS_PER_COLOR + offset).clamp(-1.0, 1.0);
+    let (from, to, t) = match t < 0.0 {
+        true => (NEUTRAL, FAST, -t),
+        false => (NEUTRAL, SLOW, t),
+    };
+    let mix = |from: u8, to: u8| (f64::from(from) + (f64::from(to) - f64::from(from)) * t) as u8;
+    RGBColor(mix(from.0, to.0), mix(from.1, to.1), mix(from.2, to.2))
+}
+
+/// Renders the heatmap of `category` into `heatmap-{category}.svg` within the output directory.
+///
+/// Every row is a runtime and every column a test case, each cell holds the time of the
+/// runtime relative to the `baseline` of the test case. Runtimes that did not run a test
+/// case are marked with ❌. Rows are sorted by the geometric mean of the cells, fastest on top.
+pub fn plot_heatmap(
+    ext_title: Option<&str>,
+    style: Style,
+    output: &Output,
+    baseline: &Baseline,
+    category: BenchCategory,
+    data: &GeomeanData,
+) -> Result<Option<String>, Box<dyn Error>> {
+    let runtimes = data.runtimes();
+    if runtimes.is_empty() {
+        // No runtime of the selected kind ran at all: nothing to plot.
+        return Ok(None);
+    }
+    let reference = match baseline {
+        Baseline::Optimal => None,
+        Baseline::Runtime(id) => match runtimes.iter().find(|vm| vm.id() == id) {
+            Some(vm) => Some(*vm),
+            None => {
+                eprintln!(
+                    "{category}/heatmap: reference runtime {id:?} not found: skipping heatmap"
+                );
+                return Ok(None);
+            }
+        },
+    };
+    let mut columns = Vec::new();
+    for (name, times) in data.cases() {
+        let column_baseline = match reference {
+            None => times.values().copied().min_by(f64::total_cmp),
+            Some(reference) => times.get(&reference).copied(),
+        };
+        let Some(column_baseline) = column_baseline else {
+            let runtime = reference.map_or("any runtime", |reference| reference.label());
+            eprintln!("{category}/heatmap: excluding test case {name:?}: not run by {runtime}");
+            continue;
+        };
+        columns.push(Column {
+            name,
+            baseline: column_baseline,
+            times,
+        });
+    }
+    if columns.is_empty() {
+        return Ok(None);
+    }
+    // Fastest runtime on top: sort by the geometric mean of the test cases each runtime ran.
+    let mut rows: Vec<(VmAndConfig, f64)> = runtimes
+        .into_iter()
+        .map(|vm| {
+            let logs: Vec<f64> = columns
+                .iter()
+                .filter_map(|column| column.ratio(vm))
+                .map(f64::ln)
+                .collect();
+            (vm, logs.iter().sum::<f64>() / logs.len() as f64)
+        })
+        .collect();
+    rows.sort_by(|lhs, rhs| lhs.1.total_cmp(&rhs.1));
+    let rows: Vec<VmAndConfig> = rows.into_iter().map(|(vm, _)| vm).collect();
+    let layout = Layout::new(output, rows.len(), columns.len());
+    let description = match reference {
+        None => String::from("Time relative to the fastest runtime per test case"),
+        Some(reference) => format!("Time relative to {}", reference.label()),
+    };
+    let heatmap = Heatmap {
+        title: &plot_title(ext_title, &format!("{category}/heatmap")),
+        description: &format!(
+            "{description} (lower is better, {} = not supported)",
+            unsupported_marker(output.format)
+        ),
+        baseline,
+        rows: &rows,
+        columns: &columns,
+    };
+    let path = output.plot_path(&format!("heatmap-{category}"))?;
+    let size = layout.size();
+    match output.format {
+        Format::Svg => {
+            let root = SVGBackend::new(&path, size).into_drawing_area();
+            draw_heatmap(root, style, output, &layout, &heatmap)?;
+        }
+        Format::Png => {
+            let root = BitMapBackend::new(&path, size).into_drawing_area();
+            draw_heatmap(root, style, output, &layout, &heatmap)?;
+        }
+    }
+    Ok(Some(path))
+}
+
+/// The contents of a heatmap.
+struct Heatmap<'a> {
+    /// The title of the heatmap.
+    title: &'a str,
+    /// The description of the cell values drawn below the heatmap.
+    description: &'a str,
+    /// The baseline the cells are relative to.
+    baseline: &'a Baseline,
+    /// The runtimes in order of the rows.
+    rows: &'a [VmAndConfig],
+    /// The test cases in order of the columns.
+    columns: &'a [Column<'a>],
+}
+
+/// The pixel dimensions of a heatmap, scaled by [`Output::font_scale`].
+struct Layout {
+    /// The width of the runtime labels to the left of the cells.
+    label_width: u32,
+    /// The height of the rotated test case names above the cells.
+    header_height: u32,
+    /// The height of the title above the header.
+    title_height: u32,
+    /// The height of the description below the cells.
+    footer_height: u32,
+    /// The width of a single cell.
+    cell_width: u32,
+    /// The height of a single cell.
+    cell_height: u32,
+    /// The number of rows.
+    rows: usize,
+    /// The number of columns.
+    columns: usize,
+    /// The total width of the heatmap, at least [`Output::width`].
+    width: u32,
+}
+
+impl Layout {
+    /// Creates the [`Layout`] of a heatmap with `rows` runtimes and `columns` test cases.
+    fn new(output: &Output, rows: usize, columns: usize) -> Self {
+        let label_width = output.scaled(400);
+        let cell_width = output.scaled(100);
+        let width = label_width + columns as u32 * cell_width + output.scaled(20);
+        Self {
+            label_width,
+            header_height: output.scaled(220),
+            title_height: output.scaled(70),
+            footer_height: output.scaled(60),
+            cell_width,
+            cell_height: output.scaled(40),
+            rows,
+            columns,
+            width: width.max(output.width),
+        }
+    }
+
+    /// Returns the total dimensions of the heatmap.
+    fn size(&self) -> (u32, u32) {
+        let cells = self.rows as u32 * self.cell_height;
+        let height = self.title_height + self.header_height + cells + self.footer_height;
+        (self.width, height)
+    }
+
+    /// Returns the horizontal offset of the cells, centering them if [`Output::width`] is wider.
+    fn left(&self) -> i32 {
+        let used = self.label_width + self.columns as u32 * self.cell_width;
+        (self.width.saturating_sub(used) / 2 + self.label_width) as i32
+    }
+
+    /// Returns the upper left corner of the cell at `row` and `column`.
+    fn cell(&self, row: usize, column: usize) -> (i32, i32) {
+        let x = self.left() + (column as u32 * self.cell_width) as i32;
+        let y = (self.title_height + self.header_height + row as u32 * self.cell_height) as i32;
+        (x, y)
+    }
+}
+
+/// Draws `heatmap` onto `root` as laid out by `layout`.
+fn draw_heatmap<DB>(
+    root: DrawingArea<DB, Shift>,
+    style: Style,
+    output: &Output,
+    layout: &Layout,
+    heatmap: &Heatmap,
+) -> Result<(), Box<dyn Error>>
+where
+    DB: DrawingBackend,
+    DB::ErrorType: 'static,
+{
+    let background = output.theme.background();
+    root.fill(background)?;
+    let center = (layout.width / 2) as i32;
+    root.draw(&Text::new(
+        heatmap.title,
+        (center, (layout.title_height / 2) as i32),
+        output
+            .text("monospace", 45)
+            .pos(Pos::new(HPos::Center, VPos::Center)),
+    ))?;
+    let (cell_width, cell_height) = (layout.cell_width as i32, layout.cell_height as i32);
+    let gap = output.scaled(10) as i32;
+    for (index, column) in heatmap.columns.iter().enumerate() {
+        let (x, y) = layout.cell(0, index);
+        // Reads bottom to top, ending right above the column.
+        root.draw(&Text::new(
+            column.name,
+            (x + cell_width / 2, y - gap),
+            output
+                .text("sans-serif", 24)
+                .transform(FontTransform::Rotate270)
+                .pos(Pos::new(HPos::Left, VPos::Center)),
+        ))?;
+    }
+    for (row, &vm) in heatmap.rows.iter().enumerate() {
+        let (x, y) = layout.cell(row, 0);
+        let highlight = style.highlights.color(vm);
+        let mut label = output
+            .text("sans-serif", 24)
+            .pos(Pos::new(HPos::Right, VPos::Center));
+        if let Some(color) = &highlight {
+            label = label.color(color);
+        }
+        root.draw(&Text::new(
+            vm.label(),
+            (x - gap, y + cell_height / 2),
+            label,
+        ))?;
+        for (index, column) in heatmap.columns.iter().enumerate() {
+            let (x, y) = layout.cell(row, index);
+            let (color, text) = match column.ratio(vm) {
+                Some(ratio) => (cell_color(ratio, heatmap.baseline), format_ratio(ratio)),
+                None => (UNSUPPORTED, String::from(unsupported_marker(output.format))),
+            };
+            root.draw(&Rectangle::new(
+                [(x, y), (x + cell_width, y + cell_height)],
+                color.filled(),
+            ))?;
+            // Separates neighboring cells of similar color.
+            root.draw(&Rectangle::new(
+                [(x, y), (x + cell_width, y + cell_height)],
+                background.stroke_width(1),
+            ))?;
+            root.draw(&Text::new(
+                text,
+                (x + cell_width / 2, y + cell_height / 2),
+                TextStyle::from(("monospace", output.scaled(18)))
+                    .color(&BLACK)
+                    .pos(Pos::new(HPos::Center, VPos::Center)),
+            ))?;
+        }
+    }
+    let (_, bottom) = layout.cell(heatmap.rows.len(), 0);
+    root.draw(&Text::new(
+        heatmap.description,
+        (center, bottom + (layout.footer_height / 2) as i32),
+        output
+            .text("sans-serif", 24)
+            .pos(Pos::new(HPos::Center, VPos::Center)),
+    ))?;
+    // To avoid the IO failure being ignored silently, we manually call the present function
+    root.present()?;
+    Ok(())
+}
+
+/// Formats the relative time `ratio` of a cell, e.g. `x2.35` or `x120`.
+fn format_ratio(ratio: f64) -> String {
+    match ratio < 100.0 {
+        true => format!("x{ratio:.02}"),
+        false => format!("x{ratio:.0}"),
+    }
+}
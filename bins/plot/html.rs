@@ -0,0 +1,297 @@
+//! Bundles the rendered plots and the decoded results into a single self-contained HTML report.
+
+use super::coremark::Score;
+use super::output::Output;
+use super::{BenchCategory, BenchGroup, Filters, Rendered, RuntimeKind, VmAndConfig};
+use std::collections::BTreeSet;
+use std::error::Error;
+use std::fmt::Write as _;
+use std::fs;
+use wasmi_benchmarks::results::format_duration_ns;
+
+/// The file name of the rendered HTML report within the output directory.
+const REPORT_NAME: &str = "index.html";
+
+/// Writes the HTML report of everything `rendered` to [`REPORT_NAME`] within the output directory.
+///
+/// The per test case and geomean plots are inlined as SVG, so the report does not depend on any
+/// other file. The results, CoreMark and support tables can be sorted by clicking a column header
+/// and filtered by runtime name and kind.
+pub fn write_report(
+    ext_title: Option<&str>,
+    output: &Output,
+    filters: &Filters,
+    rendered: &Rendered,
+    coremark: Option<&[Score]>,
+) -> Result<(), Box<dyn Error>> {
+    let title = match ext_title {
+        Some(ext_title) => format!("Wasmi Benchmarks - {ext_title}"),
+        None => String::from("Wasmi Benchmarks"),
+    };
+    let mut html = String::new();
+    writeln!(
+        html,
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+        <title>{}</title>\n<style>{STYLE}</style>\n</head>\n<body>\n<h1>{}</h1>",
+        escape(&title),
+        escape(&title),
+    )?;
+    html.push_str(
+        "<div class=\"filters\">\
+        <input id=\"runtime-filter\" type=\"search\" placeholder=\"Filter runtimes\" oninput=\"applyFilters()\">\
+        <select id=\"kind-filter\" onchange=\"applyFilters()\">\
+        <option value=\"\">All kinds</option>\
+        <option value=\"jit\">JIT</option>\
+        <option value=\"interpreter\">Interpreter</option>\
+        <option value=\"unknown\">Unknown</option>\
+        </select></div>\n",
+    );
+    if !rendered.geomeans.is_empty() {
+        html.push_str("<h2>Geomean</h2>\n");
+        for path in &rendered.geomeans {
+            push_svg(&mut html, path)?;
+        }
+    }
+    if !rendered.heatmaps.is_empty() {
+        html.push_str("<h2>Heatmap</h2>\n");
+        for path in &rendered.heatmaps {
+            push_svg(&mut html, path)?;
+        }
+    }
+    if !rendered.memory.is_empty() {
+        html.push_str("<h2>Memory</h2>\n");
+        for path in &rendered.memory {
+            push_svg(&mut html, path)?;
+        }
+    }
+    if !rendered.perf.is_empty() {
+        html.push_str("<h2>Hardware Counters</h2>\n");
+        for path in &rendered.perf {
+            push_svg(&mut html, path)?;
+        }
+    }
+    if let Some(path) = &rendered.startup_sizes {
+        html.push_str("<h2>Startup by Module Size</h2>\n");
+        push_svg(&mut html, path)?;
+    }
+    if !rendered.compiled.is_empty() {
+        html.push_str("<h2>Compiled Size</h2>\n");
+        for path in &rendered.compiled {
+            push_svg(&mut html, path)?;
+        }
+    }
+    if let Some(scores) = coremark {
+        html.push_str("<h2>CoreMark</h2>\n");
+        if let Some(path) = &rendered.coremark {
+            push_svg(&mut html, path)?;
+        }
+        push_coremark(&mut html, scores)?;
+    }
+    for category in [
+        BenchCategory::Execute,
+        BenchCategory::Startup,
+        BenchCategory::ColdStart,
+        BenchCategory::FirstCall,
+    ] {
+        let groups: Vec<&BenchGroup> = rendered
+            .groups
+            .iter()
+            .filter(|group| group.category == category)
+            .collect();
+        if groups.is_empty() {
+            continue;
+        }
+        push_results(&mut html, category, filters, &groups)?;
+    }
+    if !rendered.plots.is_empty() {
+        html.push_str("<h2>Test Cases</h2>\n");
+        for path in &rendered.plots {
+            let name = output.plot_name(path);
+            writeln!(html, "<details><summary>{}</summary>", escape(name))?;
+            push_svg(&mut html, path)?;
+            html.push_str("</details>\n");
+        }
+    }
+    writeln!(html, "<script>{SCRIPT}</script>\n</body>\n</html>")?;
+    let path = output.file_path(REPORT_NAME);
+    fs::create_dir_all(&output.dir)?;
+    fs::write(&path, html)?;
+    eprintln!("rendered HTML report to {}", path.display());
+    Ok(())
+}
+
+/// Inlines the rendered SVG file at `path`.
+fn push_svg(html: &mut String, path: &str) -> Result<(), Box<dyn Error>> {
+    let svg = fs::read_to_string(path)?;
+    // An XML declaration is only valid at the start of a standalone document.
+    let svg = match svg.find("<svg") {
+        Some(start) => &svg[start..],
+        None => &svg[..],
+    };
+    writeln!(html, "<figure>{svg}</figure>")?;
+    Ok(())
+}
+
+/// Pushes the sortable table of the CoreMark `scores`.
+fn push_coremark(html: &mut String, scores: &[Score]) -> Result<(), Box<dyn Error>> {
+    html.push_str(
+        "<table class=\"sortable\">\n<thead><tr>\
+        <th>Runtime</th><th>Score (higher is better)</th></tr></thead>\n<tbody>\n",
+    );
+    for score in scores {
+        let kind = score.vm.map(|vm| kind_name(vm.kind())).unwrap_or_default();
+        let label = score.label();
+        writeln!(
+            html,
+            "<tr data-runtime=\"{}\" data-kind=\"{kind}\"><td>{}</td>\
+            <td data-value=\"{value}\">{value:.0}</td></tr>",
+            escape(&format!("{} {label}", score.name).to_lowercase()),
+            escape(label),
+            value = score.score,
+        )?;
+    }
+    html.push_str("</tbody>\n</table>\n");
+    Ok(())
+}
+
+/// Pushes the raw results and the support matrix of all `groups` of `category`.
+///
+/// Both tables have a row per runtime and a column per test case.
+fn push_results(
+    html: &mut String,
+    category: BenchCategory,
+    filters: &Filters,
+    groups: &[&BenchGroup],
+) -> Result<(), Box<dyn Error>> {
+    let runtimes: BTreeSet<VmAndConfig> = groups
+        .iter()
+        .flat_map(|group| group.results.keys().copied())
+        .filter(|&vm| filters.keeps(vm))
+        .collect();
+    let header = |html: &mut String| -> Result<(), Box<dyn Error>> {
+        html.push_str("<table class=\"sortable\">\n<thead><tr><th>Runtime</th>");
+        for group in groups {
+            write!(html, "<th>{}</th>", escape(&group.name))?;
+        }
+        html.push_str("</tr></thead>\n<tbody>\n");
+        Ok(())
+    };
+    let row_start = |html: &mut String, vm: VmAndConfig| {
+        write!(
+            html,
+            "<tr data-runtime=\"{}\" data-kind=\"{}\"><td>{}</td>",
+            escape(&vm.label().to_lowercase()),
+            kind_name(vm.kind()),
+            escape(vm.label()),
+        )
+    };
+
+    writeln!(html, "<h2>Results: {category}</h2>")?;
+    header(html)?;
+    for &vm in &runtimes {
+        row_start(html, vm)?;
+        for group in groups {
+            match group.results.get(&vm) {
+                Some(typical) => {
+                    let time = typical.estimate;
+                    write!(
+                        html,
+                        "<td data-value=\"{time}\">{}</td>",
+                        format_duration_ns(time)
+                    )?;
+                }
+                None => html.push_str("<td data-value=\"\">-</td>"),
+            }
+        }
+        html.push_str("</tr>\n");
+    }
+    html.push_str("</tbody>\n</table>\n");
+
+    writeln!(html, "<h2>Support: {category}</h2>")?;
+    header(html)?;
+    for &vm in &runtimes {
+        row_start(html, vm)?;
+        for group in groups {
+            let (value, cell) = match group.results.contains_key(&vm) {
+                true => (1, "✅"),
+                false => (0, "❌"),
+            };
+            write!(html, "<td data-value=\"{value}\">{cell}</td>")?;
+        }
+        html.push_str("</tr>\n");
+    }
+    html.push_str("</tbody>\n</table>\n");
+    Ok(())
+}
+
+/// Returns the name of `kind` as used by the kind filter of the report.
+fn kind_name(kind: Option<RuntimeKind>) -> &'static str {
+    match kind {
+        Some(RuntimeKind::Jit) => "jit",
+        Some(RuntimeKind::Interpreter) => "interpreter",
+        None => "unknown",
+    }
+}
+
+/// Escapes `text` for use in HTML element content and attribute values.
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// The stylesheet of the report.
+const STYLE: &str = "
+body { font-family: sans-serif; margin: 2em auto; max-width: 1400px; color: #222; }
+.filters { position: sticky; top: 0; background: white; padding: 0.5em 0; }
+table { border-collapse: collapse; margin: 1em 0; font-size: 0.85em; display: block; overflow-x: auto; }
+th, td { border: 1px solid #ddd; padding: 0.3em 0.6em; text-align: right; white-space: nowrap; }
+th { background: #f4f4f4; cursor: pointer; user-select: none; }
+th[data-order=asc]::after { content: ' ▲'; }
+th[data-order=desc]::after { content: ' ▼'; }
+td:first-child, th:first-child { text-align: left; }
+figure { margin: 1em 0; }
+figure svg { max-width: 100%; height: auto; }
+summary { cursor: pointer; padding: 0.2em 0; }
+";
+
+/// The script sorting the tables by a clicked column and applying the runtime filters.
+const SCRIPT: &str = r#"
+document.querySelectorAll("table.sortable th").forEach(th => {
+    th.addEventListener("click", () => {
+        const body = th.closest("table").tBodies[0];
+        const ascending = th.dataset.order !== "asc";
+        th.closest("tr").querySelectorAll("th").forEach(other => delete other.dataset.order);
+        th.dataset.order = ascending ? "asc" : "desc";
+        const key = row => {
+            const cell = row.cells[th.cellIndex];
+            if (cell.dataset.value === undefined) return cell.textContent;
+            return cell.dataset.value === "" ? null : parseFloat(cell.dataset.value);
+        };
+        const rows = [...body.rows].sort((a, b) => {
+            const [x, y] = [key(a), key(b)];
+            // Missing values always sort last.
+            if (x === null) return y === null ? 0 : 1;
+            if (y === null) return -1;
+            const order = typeof x === "number" ? x - y : x.localeCompare(y);
+            return ascending ? order : -order;
+        });
+        rows.forEach(row => body.appendChild(row));
+    });
+});
+function applyFilters() {
+    const text = document.getElementById("runtime-filter").value.toLowerCase();
+    const kind = document.getElementById("kind-filter").value;
+    document.querySelectorAll("tr[data-runtime]").forEach(row => {
+        row.hidden = !row.dataset.runtime.includes(text) || (kind !== "" && row.dataset.kind !== kind);
+    });
+}
+"#;
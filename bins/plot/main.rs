@@ -0,0 +1,989 @@
+use benchmark_utils::RuntimeKind;
+use clap::Parser;
+use geomean::{Baseline, Coverage, GeomeanData};
+use memory::MemoryData;
+use output::{Format, Output, Theme};
+use perf::PerfData;
+use plotters::coord::Shift;
+use plotters::coord::ranged1d::{Ranged, SegmentedCoord, ValueFormatter};
+use plotters::coord::types::RangedCoordusize;
+use plotters::prelude::*;
+use plotters::style::text_anchor::{HPos, Pos, VPos};
+use select::{Glob, Highlights};
+use sizes::CompiledSizes;
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt::{self, Display};
+use std::io::{self, BufRead as _, IsTerminal as _};
+use std::path::PathBuf;
+use std::str::FromStr;
+use vm::VmAndConfig;
+use wasmi_benchmarks::results::{
+    BenchId, Estimate, Measurement, Message, ModuleInfo, Throughput, format_bytes,
+    format_duration_ns,
+};
+
+mod coremark;
+mod geomean;
+mod heatmap;
+mod html;
+mod memory;
+mod output;
+mod perf;
+mod select;
+mod sizes;
+mod vm;
+
+/// Scaling of the relative-time axis in the rendered plots.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, clap::ValueEnum)]
+enum Scale {
+    /// Logarithmic scaling.
+    Log,
+    /// Linear scaling.
+    Linear,
+}
+
+/// How measured times are expressed in the rendered plots.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default, clap::ValueEnum)]
+enum Time {
+    /// Values relative to the fastest runtime in the group (e.g. `x2.35`).
+    #[default]
+    Relative,
+    /// Absolute measured durations (e.g. `5.23 ms`).
+    Absolute,
+}
+
+/// The unit of the measured values plotted as bars.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Unit {
+    /// Durations in nanoseconds.
+    Nanoseconds,
+    /// Amounts of memory in bytes.
+    Bytes,
+    /// Plain counts of events, e.g. retired instructions.
+    Count,
+}
+
+impl Unit {
+    /// Formats the `value` of this unit adaptively, e.g. as `5.23 ms`, `1.50 MiB` or `12.3 k`.
+    fn format(self, value: f64) -> String {
+        match self {
+            Unit::Nanoseconds => format_duration_ns(value),
+            Unit::Bytes => format_bytes(value),
+            Unit::Count => format_count(value),
+        }
+    }
+}
+
+/// Formats a count with decimal prefixes, e.g. `0.012`, `12.3` or `4.56 M`.
+fn format_count(count: f64) -> String {
+    let mut value = count;
+    let mut prefix = "";
+    for next in ["k", "M", "G", "T"] {
+        if value < 1000.0 {
+            break;
+        }
+        value /= 1000.0;
+        prefix = next;
+    }
+    match prefix {
+        // Small counts per Wasm op, e.g. of branch misses, still differ in their decimals.
+        "" if value < 10.0 => format!("{value:.03}"),
+        "" => format!("{value:.01}"),
+        prefix => format!("{value:.02} {prefix}"),
+    }
+}
+
+/// Excludes a kind of Wasm runtime from the rendered plots.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default, clap::ValueEnum)]
+enum Filter {
+    /// Include all Wasm runtimes.
+    #[default]
+    None,
+    /// Exclude all JIT-compiling Wasm runtimes and those of unknown kind.
+    Jit,
+    /// Exclude all interpreter-based Wasm runtimes and those of unknown kind.
+    Interpreter,
+    /// Exclude all Wasm runtimes that a newer supported version supersedes.
+    Outdated,
+}
+
+impl Filter {
+    /// Returns `true` if the given `vm` passes this filter.
+    fn keeps(self, vm: VmAndConfig) -> bool {
+        match self {
+            Filter::None => true,
+            Filter::Jit => vm.kind() == Some(RuntimeKind::Interpreter),
+            Filter::Interpreter => vm.kind() == Some(RuntimeKind::Jit),
+            Filter::Outdated => !vm.is_outdated(),
+        }
+    }
+}
+
+/// The [`Filter`]s and selectors to apply, combined conjunctively.
+#[derive(Debug, Default, Clone)]
+struct Filters {
+    filters: Vec<Filter>,
+    /// Keeps only the Wasm runtimes selected by any of these, if not empty.
+    only: Vec<Glob>,
+    /// Excludes the Wasm runtimes selected by any of these.
+    exclude: Vec<Glob>,
+    /// Keeps only the test cases matched by any of these, if not empty.
+    cases: Vec<Glob>,
+}
+
+impl Filters {
+    /// Returns `true` if the given `vm` passes all of the filters.
+    fn keeps(&self, vm: VmAndConfig) -> bool {
+        self.filters.iter().all(|filter| filter.keeps(vm))
+            && (self.only.is_empty() || self.only.iter().any(|glob| glob.selects(vm)))
+            && !self.exclude.iter().any(|glob| glob.selects(vm))
+    }
+
+    /// Returns `true` if the test case of `bench_group` passes the case filters.
+    ///
+    /// Matches either the test case name, e.g. `fib-*`, or along with its category,
+    /// e.g. `execute/fib-*`.
+    fn keeps_case(&self, bench_group: &BenchGroup) -> bool {
+        let id = format!("{}/{}", bench_group.category, bench_group.name);
+        self.cases.is_empty()
+            || self
+                .cases
+                .iter()
+                .any(|glob| glob.matches(&bench_group.name) || glob.matches(&id))
+    }
+}
+
+/// The rendering options shared by all plots.
+#[derive(Debug, Copy, Clone)]
+struct Style<'a> {
+    /// Scaling of the relative-time axis.
+    scale: Scale,
+    /// Whether to plot relative or absolute times.
+    time: Time,
+    /// The Wasm runtimes to highlight.
+    highlights: &'a Highlights,
+}
+
+/// Renders Criterion benchmark results (read as JSON from stdin) into SVG or PNG plots.
+#[derive(Debug, Parser)]
+struct Args {
+    /// Optional external title appended to each plot's title.
+    title: Option<String>,
+    /// Scaling of the relative-time axis.
+    #[arg(long, value_enum, default_value_t = Scale::Log)]
+    scale: Scale,
+    /// Whether to plot relative or absolute times.
+    #[arg(long, value_enum, default_value_t = Time::Relative)]
+    time: Time,
+    /// Excludes kinds of Wasm runtimes from the plots.
+    ///
+    /// May be given repeatedly or as a comma separated list.
+    #[arg(long = "filter", value_enum, value_delimiter = ',')]
+    filters: Vec<Filter>,
+    /// Keeps only the Wasm runtimes selected by any of the given globs, e.g. `wasmi-*`.
+    ///
+    /// A glob selects a Wasm runtime if it matches either its ID, e.g. `wasmi-v2.eager.checked`,
+    /// or just the runtime, e.g. `wasmi-v2`. May be given repeatedly or as a comma separated list.
+    #[arg(long, value_delimiter = ',')]
+    only: Vec<Glob>,
+    /// Excludes the Wasm runtimes selected by any of the given globs, e.g. `*.unchecked`.
+    ///
+    /// May be given repeatedly or as a comma separated list.
+    #[arg(long, value_delimiter = ',')]
+    exclude: Vec<Glob>,
+    /// Keeps only the test cases matched by any of the given globs, e.g. `fib-*` or `startup/*`.
+    ///
+    /// May be given repeatedly or as a comma separated list.
+    #[arg(long = "case", value_delimiter = ',')]
+    cases: Vec<Glob>,
+    /// Highlights the Wasm runtimes selected by the given globs, e.g. `wasmi-v2.*,wasm3.*`.
+    ///
+    /// Each glob is highlighted in its own color and explained by a legend if there are several.
+    /// Use `none` to disable highlighting.
+    #[arg(long, value_delimiter = ',', default_value = "wasmi-v2")]
+    highlight: Vec<Glob>,
+    /// The directory the plots are rendered into.
+    #[arg(long, default_value = "target/wasmi-benchmarks")]
+    out_dir: PathBuf,
+    /// The file format of the rendered plots.
+    #[arg(long, value_enum, default_value_t = Format::Svg)]
+    format: Format,
+    /// The width of the rendered plots in pixels.
+    ///
+    /// The height follows from the number of plotted runtimes.
+    #[arg(long, default_value_t = 1280)]
+    width: u32,
+    /// Scales all font sizes, and the bar heights and margins along with them.
+    ///
+    /// For example, `--width 2560 --font-scale 2` renders a high resolution version of the defaults.
+    #[arg(long, default_value_t = 1.0)]
+    font_scale: f64,
+    /// The color theme of the rendered plots.
+    #[arg(long, value_enum, default_value_t = Theme::Light)]
+    theme: Theme,
+    /// Additionally bundles all plots and results into `index.html` within the output directory.
+    ///
+    /// The report is a single self-contained HTML file with sortable tables.
+    /// Requires `--format svg` since the plots are inlined.
+    #[arg(long)]
+    html: bool,
+    /// The runtime the geomean plots are relative to: `optimal` or a runtime ID.
+    ///
+    /// The theoretical `optimal` runtime is the fastest runtime of each test case,
+    /// a runtime ID such as `wasmi-v2.eager.checked` plots all others relative to it.
+    #[arg(long, default_value = "optimal")]
+    geomean_baseline: Baseline,
+    /// Weights test cases in the geomean plots, e.g. `execute/fibonacci-*=0.5`.
+    ///
+    /// The first matching glob applies, test cases without one are weighted `1`
+    /// and a weight of `0` excludes them. May be given repeatedly or as a comma separated list.
+    #[arg(long = "geomean-weight", value_delimiter = ',', value_parser = geomean::parse_weight)]
+    geomean_weights: Vec<(Glob, f64)>,
+    /// How the geomean plots average runtimes that did not run every test case.
+    #[arg(long, value_enum, default_value_t = Coverage::Common)]
+    geomean_coverage: Coverage,
+    /// The runtime the heatmap cells are relative to: `optimal` or a runtime ID.
+    ///
+    /// The heatmaps show all runtimes and test cases of a category at a glance.
+    #[arg(long, default_value = "optimal")]
+    heatmap_baseline: Baseline,
+    /// Renders the CoreMark scores of the given file, e.g. `data/apple-m2-pro/coremark.csv`.
+    ///
+    /// Accepts the JSON summary printed by the `coremark` binary or a CSV file with `runtime`
+    /// and `score` columns. No Criterion results are read if stdin is a terminal.
+    #[arg(long)]
+    coremark: Option<PathBuf>,
+}
+
+#[derive(Debug, Copy, Clone)]
+pub struct BenchEntry {
+    pub vm: VmAndConfig,
+    /// The measured value in [`BenchEntry::unit`], usually a time in nanoseconds.
+    pub time: f64,
+    /// The unit of the measured value.
+    pub unit: Unit,
+    /// The number of averaged test cases the runtime ran out of all of them, if not all.
+    pub coverage: Option<(usize, usize)>,
+    /// The work done per iteration, rendered as rate alongside the time.
+    pub throughput: Option<Throughput>,
+}
+
+impl BenchEntry {
+    /// Returns the value plotted for this entry given the fastest time `min` (nanoseconds).
+    ///
+    /// In [`Time::Relative`] mode this is the ratio to the fastest runtime, in
+    /// [`Time::Absolute`] mode it is the raw time in nanoseconds.
+    fn value(&self, min: f64, time: Time) -> f64 {
+        match time {
+            Time::Relative => self.time / min,
+            Time::Absolute => self.time,
+        }
+    }
+
+    /// Returns the label drawn at the end of this entry's bar.
+    fn label(&self, min: f64, time: Time) -> String {
+        let mut label = match time {
+            Time::Relative => format!("x{:.02}", self.value(min, time)),
+            Time::Absolute => self.unit.format(self.time),
+        };
+        if let Some(throughput) = self.throughput {
+            label = format!("{label} ({})", throughput.format_rate(self.time));
+        }
+        match self.coverage {
+            Some((covered, total)) => format!("{label} ({covered}/{total})"),
+            None => label,
+        }
+    }
+}
+
+fn plot_for_data(
+    ext_title: Option<&str>,
+    style: Style,
+    output: &Output,
+    filters: &Filters,
+    bench_group: &BenchGroup,
+) -> Result<Option<String>, Box<dyn Error>> {
+    let data = bench_group.entries(filters);
+    if data.is_empty() {
+        // No runtime of the selected kind ran in this group: nothing to plot.
+        return Ok(None);
+    }
+    // Bars are plotted relative to the fastest runtime of this group.
+    let min = data
+        .iter()
+        .map(|entry| entry.time)
+        .min_by(f64::total_cmp)
+        .unwrap_or(1.0);
+    let kind = match style.time {
+        Time::Relative => "Relative Time",
+        Time::Absolute => "Time",
+    };
+    let category = bench_group.category;
+    let name = &bench_group.name;
+    let path = output.plot_path(&format!("{category}/{name}"))?;
+    render_plot(
+        &plot_title(ext_title, &format!("{category}/{name}")),
+        &path,
+        style,
+        output,
+        kind,
+        min,
+        data,
+    )?;
+    Ok(Some(path))
+}
+
+/// Appends the optional external title to the plot's `test_id`.
+fn plot_title(ext_title: Option<&str>, test_id: &str) -> String {
+    match ext_title {
+        Some(ext_title) => format!("{test_id} - {ext_title}"),
+        None => test_id.to_string(),
+    }
+}
+
+/// Renders `data` as a horizontal bar chart into the file at `path`.
+///
+/// In [`Time::Relative`] mode every bar is plotted as `entry.time / min`, so
+/// `min` is the baseline the plot is relative to: the fastest runtime of a
+/// benchmark group for the per-test-case plots, or `1.0` for the geomean plots
+/// whose entries already are relative values.
+fn render_plot(
+    title: &str,
+    path: &str,
+    style: Style,
+    output: &Output,
+    kind: &str,
+    min: f64,
+    mut data: Vec<BenchEntry>,
+) -> Result<(), Box<dyn Error>> {
+    // Slowest runtime first so the bars form a descending staircase.
+    data.sort_by(|lhs, rhs| rhs.time.total_cmp(&lhs.time));
+    let size = output.bar_chart_size(data.len());
+    match output.format {
+        Format::Svg => {
+            let root = SVGBackend::new(path, size).into_drawing_area();
+            draw_plot(root, title, style, output, kind, min, &data)
+        }
+        Format::Png => {
+            let root = BitMapBackend::new(path, size).into_drawing_area();
+            draw_plot(root, title, style, output, kind, min, &data)
+        }
+    }
+}
+
+/// Draws the titled bar chart of `data` onto `root`, see [`render_plot`].
+///
+/// This is generic over the drawing backend so it can render all [`Format`]s.
+fn draw_plot<DB>(
+    root: DrawingArea<DB, Shift>,
+    title: &str,
+    style: Style,
+    output: &Output,
+    kind: &str,
+    min: f64,
+    data: &[BenchEntry],
+) -> Result<(), Box<dyn Error>>
+where
+    DB: DrawingBackend,
+    DB::ErrorType: 'static,
+{
+    let max = data
+        .iter()
+        .map(|entry| entry.time)
+        .max_by(f64::total_cmp)
+        .unwrap_or(1.0);
+    // The longest bar reaches the slowest runtime's plotted value: its relative
+    // time (`max / min`) in relative mode or its absolute time in absolute mode.
+    let max_value = match style.time {
+        Time::Relative => max / min,
+        Time::Absolute => max,
+    };
+
+    root.fill(output.theme.background())?;
+    let root = root.margin(5, 5, 5, 5).titled(
+        title,
+        output
+            .text("monospace", 45)
+            .pos(Pos::new(HPos::Center, VPos::Center)),
+    )?;
+    // Leaves room for the rates appended to the value labels of the longest bars.
+    let margin_right = match data.iter().any(|entry| entry.throughput.is_some()) {
+        true => output.scaled(320),
+        false => output.scaled(200),
+    };
+    let mut builder = ChartBuilder::on(&root);
+    builder
+        .x_label_area_size(output.scaled(75))
+        .y_label_area_size(output.scaled(400))
+        .margin_right(margin_right)
+        .margin_top(output.scaled(25));
+    let y_axis = (0usize..data.len() - 1).into_segmented();
+
+    // In log scaling the bars start at a lower bound below the fastest value so
+    // the fastest bar stays visible: `0.5` (below a relative min of `1.0`) in
+    // relative mode, or `min * 0.5` (below the absolute min) in absolute mode.
+    // Geomeans relative to a reference runtime may be faster than `1.0` though.
+    let fastest = data
+        .iter()
+        .map(|entry| entry.time)
+        .min_by(f64::total_cmp)
+        .unwrap_or(min);
+    let log_baseline = match style.time {
+        Time::Relative => f64::min(0.5, fastest / min * 0.5),
+        Time::Absolute => min * 0.5,
+    };
+
+    // Log and linear scaling produce different chart coordinate types, so the
+    // shared drawing logic lives in the generic `draw_chart` helper. The two
+    // scales also differ in how the axis maximum is derived: the log scale is
+    // floored to a full decade (`10.0`) in relative mode so it always shows a
+    // complete range of gridlines, whereas the linear scale is fit tightly to
+    // the data (plus a small headroom) so the bars are not squeezed into a
+    // fraction of the plot. Linear scaling also starts the axis (and the bar
+    // baseline) at `0.0` instead of the log baseline.
+    match style.scale {
+        Scale::Log => {
+            let axis_max = match style.time {
+                Time::Relative => core::cmp::max_by(10.0, max_value, f64::total_cmp),
+                Time::Absolute => max_value,
+            };
+            let mut chart =
+                builder.build_cartesian_2d((log_baseline..axis_max * 1.05).log_scale(), y_axis)?;
+            draw_chart(
+                &mut chart,
+                data,
+                min,
+                style,
+                output,
+                log_baseline,
+                &format!("{kind} (lower is better, logarithmic scale)"),
+            )?;
+        }
+        Scale::Linear => {
+            let mut chart = builder.build_cartesian_2d(0.0_f64..max_value * 1.05, y_axis)?;
+            draw_chart(
+                &mut chart,
+                data,
+                min,
+                style,
+                output,
+                0.0,
+                &format!("{kind} (lower is better, linear scale)"),
+            )?;
+        }
+    }
+    // To avoid the IO failure being ignored silently, we manually call the present function
+    root.present()?;
+    Ok(())
+}
+
+/// Draws the mesh, the bars and their value labels onto `chart`.
+///
+/// This is generic over the X coordinate type so it can render both the
+/// logarithmic and the linear chart produced in [`draw_plot`].
+fn draw_chart<'a, DB, X>(
+    chart: &mut ChartContext<'a, DB, Cartesian2d<X, SegmentedCoord<RangedCoordusize>>>,
+    data: &[BenchEntry],
+    min: f64,
+    style: Style,
+    output: &Output,
+    baseline: f64,
+    x_desc: &str,
+) -> Result<(), Box<dyn Error>>
+where
+    DB: DrawingBackend + 'a,
+    DB::ErrorType: 'static,
+    X: Ranged<ValueType = f64> + ValueFormatter<f64>,
+{
+    // We want to draw the Wasm runtime names instead of the numbers.
+    let y_label_formatter = |coord: &SegmentValue<usize>| match coord {
+        SegmentValue::CenterOf(n) => data[*n].vm.label().to_string(),
+        SegmentValue::Exact(_n) => unreachable!(),
+        SegmentValue::Last => unreachable!(),
+    };
+    // In absolute mode the axis values are nanoseconds or bytes, so format the ticks
+    // adaptively as ns/µs/ms/s or B/KiB/MiB; relative mode keeps plotters' default numbers.
+    let unit = data.first().map_or(Unit::Nanoseconds, |entry| entry.unit);
+    let x_label_formatter = |value: &f64| unit.format(*value);
+
+    let foreground = output.theme.foreground();
+    let mut mesh = chart.configure_mesh();
+    mesh.disable_y_mesh()
+        .x_max_light_lines(1)
+        .axis_style(foreground)
+        .bold_line_style(foreground.mix(0.15))
+        .y_desc("") // WebAssembly Runtime
+        .x_desc(x_desc)
+        .y_label_formatter(&y_label_formatter)
+        .x_label_style(output.text("sans-serif", 20))
+        .y_label_style(output.text("sans-serif", 30))
+        .axis_desc_style(output.text("sans-serif", 35))
+        .x_labels(3)
+        .y_labels(data.len());
+    if let Time::Absolute = style.time {
+        mesh.x_label_formatter(&x_label_formatter);
+    }
+    mesh.draw()?;
+
+    chart.draw_series(
+        Histogram::horizontal(chart)
+            .style_func(|x, _bar_height| match x {
+                SegmentValue::Exact(n) => data[*n].vm.color(style.highlights).filled(),
+                SegmentValue::CenterOf(_n) => unreachable!(),
+                SegmentValue::Last => unreachable!(),
+            })
+            .margin(output.scaled(15))
+            .baseline(baseline)
+            .data(
+                data.iter()
+                    .enumerate()
+                    .map(|(index, entry)| (index, entry.value(min, style.time))),
+            ),
+    )?;
+
+    let label_offset = (output.scaled(10) as i32, output.scaled(2) as i32);
+    chart.draw_series(data.iter().enumerate().map(|(index, &entry)| {
+        let value = entry.value(min, style.time);
+        // Anchor the label at the bar's end and offset it by a fixed pixel
+        // amount so the gap between bar and label is identical for every bar,
+        // regardless of the runtime's value, the axis range or the scaling.
+        //
+        // The font size is kept below the bar thickness (bars render ~20px tall:
+        // a 50px row minus the histogram's 15px margin on each side) so the label
+        // sits within the bar instead of overhanging it.
+        //
+        // `VPos::Center` centers on the font's x-height, but the labels are
+        // digits (cap-height, no descenders) whose optical center is a couple of
+        // pixels higher, so nudge the label down slightly to sit on the bar's
+        // vertical center.
+        EmptyElement::at((value, SegmentValue::CenterOf(index)))
+            + Text::new(
+                entry.label(min, style.time),
+                label_offset,
+                output
+                    .text("monospace", 22)
+                    .pos(Pos::new(HPos::Left, VPos::Center)),
+            )
+    }))?;
+    // The fastest runtimes with the shortest bars are at the top.
+    draw_legend(chart, style, output, SeriesLabelPosition::UpperRight)?;
+    Ok(())
+}
+
+/// Draws the legend of the highlight colors onto `chart` if there are several.
+///
+/// Lists every highlight glob with its color followed by the colors of the
+/// remaining JIT and interpreter Wasm runtimes.
+fn draw_legend<'a, DB, X>(
+    chart: &mut ChartContext<'a, DB, Cartesian2d<X, SegmentedCoord<RangedCoordusize>>>,
+    style: Style,
+    output: &Output,
+    position: SeriesLabelPosition,
+) -> Result<(), Box<dyn Error>>
+where
+    DB: DrawingBackend + 'a,
+    DB::ErrorType: 'static,
+    X: Ranged<ValueType = f64>,
+{
+    if !style.highlights.needs_legend() {
+        return Ok(());
+    }
+    let others = [
+        ("other JITs", VmAndConfig::BLUE),
+        ("other interpreters", VmAndConfig::TEAL),
+    ];
+    let size = output.scaled(10) as i32;
+    for (label, color) in style.highlights.legend().chain(others) {
+        chart
+            .draw_series(std::iter::empty::<EmptyElement<_, DB>>())?
+            .label(label)
+            .legend(move |(x, y)| {
+                Rectangle::new([(x, y - size), (x + 2 * size, y + size)], color.filled())
+            });
+    }
+    chart
+        .configure_series_labels()
+        .position(position)
+        .margin(output.scaled(15))
+        .legend_area_size(output.scaled(30))
+        .label_font(output.text("sans-serif", 24))
+        .background_style(output.theme.background().mix(0.9))
+        .border_style(output.theme.foreground().mix(0.3))
+        .draw()?;
+    Ok(())
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum BenchCategory {
+    Execute,
+    Startup,
+    ColdStart,
+    FirstCall,
+}
+
+#[derive(Debug)]
+pub struct FromStrError {
+    message: String,
+}
+
+impl Error for FromStrError {}
+
+impl<S> From<S> for FromStrError
+where
+    S: Into<String>,
+{
+    fn from(message: S) -> Self {
+        FromStrError {
+            message: message.into(),
+        }
+    }
+}
+
+impl Display for FromStrError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.message.fmt(f)
+    }
+}
+
+impl FromStr for BenchCategory {
+    type Err = FromStrError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        match input {
+            "execute" => Ok(Self::Execute),
+            "startup" => Ok(Self::Startup),
+            "cold-start" => Ok(Self::ColdStart),
+            "first-call" => Ok(Self::FirstCall),
+            _ => Err(FromStrError::from(format!(
+                "invalid BenchCategory: {input}"
+            ))),
+        }
+    }
+}
+
+impl fmt::Display for BenchCategory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BenchCategory::Execute => "execute".fmt(f),
+            BenchCategory::Startup => "startup".fmt(f),
+            BenchCategory::ColdStart => "cold-start".fmt(f),
+            BenchCategory::FirstCall => "first-call".fmt(f),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct BenchGroup {
+    pub category: BenchCategory,
+    pub name: String,
+    /// The typical time of each Wasm runtime, normalized to nanoseconds.
+    pub results: BTreeMap<VmAndConfig, Estimate>,
+    pub input: Option<String>,
+    /// The work done per iteration, if the test case declares it.
+    pub throughput: Option<Throughput>,
+}
+
+impl BenchGroup {
+    /// Returns the measured times of all runtimes of this group that pass `filters`.
+    fn entries(&self, filters: &Filters) -> Vec<BenchEntry> {
+        self.results
+            .iter()
+            .filter(|&(&vm, _)| filters.keeps(vm))
+            .map(|(&vm, typical)| BenchEntry {
+                vm,
+                time: typical.estimate,
+                unit: Unit::Nanoseconds,
+                coverage: None,
+                throughput: self.throughput,
+            })
+            .collect()
+    }
+}
+
+/// Everything decoded and rendered from the benchmark results, bundled by the HTML report.
+#[derive(Debug, Default)]
+struct Rendered {
+    /// All decoded benchmark groups in order of appearance.
+    groups: Vec<BenchGroup>,
+    /// The paths of the rendered per test case plots in order of appearance.
+    plots: Vec<String>,
+    /// The paths of the rendered geomean plots, one per category.
+    geomeans: Vec<String>,
+    /// The paths of the rendered heatmaps, one per category.
+    heatmaps: Vec<String>,
+    /// The paths of the rendered memory usage plots in order of appearance.
+    memory: Vec<String>,
+    /// The paths of the rendered hardware performance counter plots in order of appearance.
+    perf: Vec<String>,
+    /// The paths of the rendered compiled size plots, one per startup test case.
+    compiled: Vec<String>,
+    /// The path of the rendered plot of startup times by module size.
+    startup_sizes: Option<String>,
+    /// The path of the rendered CoreMark plot.
+    coremark: Option<String>,
+}
+
+/// Decodes the `cargo criterion --message-format=json` output read from stdin and renders it.
+///
+/// Only `benchmark-complete` messages carry results, `memory-complete` messages the memory
+/// usage, `perf-complete` messages the hardware performance counters, `module-info` messages the
+/// sizes of the startup modules and `compiled-size` messages the sizes of their compiled
+/// artifacts: all other messages, including the build messages of Cargo, are skipped. Results are grouped by their `{category}/{case}`
+/// instead of relying on `group-complete` messages, so interleaved and interrupted runs
+/// decode just fine. Runs concatenated into the same input are merged, where the latest
+/// result of a benchmark replaces earlier ones.
+fn decode_stdin(
+    ext_title: Option<&str>,
+    style: Style,
+    output: &Output,
+    filters: &Filters,
+    geomean_options: &geomean::Options,
+    heatmap_baseline: &Baseline,
+) -> Result<Rendered, Box<dyn Error>> {
+    let mut groups: Vec<BenchGroup> = Vec::new();
+    let mut modules: BTreeMap<String, ModuleInfo> = BTreeMap::new();
+    let mut memory_data = MemoryData::default();
+    let mut perf_data = PerfData::default();
+    let mut compiled_sizes = CompiledSizes::default();
+    let mut replaced = 0;
+    for (index, line) in io::stdin().lock().lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let line_error = |error: &dyn Display| format!("<stdin>:{}: {error}", index + 1);
+        let message = Message::decode(&line).map_err(|error| line_error(&error))?;
+        let measurement = match message {
+            Some(Message::Benchmark(measurement)) => measurement,
+            Some(Message::ModuleInfo(module)) => {
+                modules.insert(module.group.clone(), module);
+                continue;
+            }
+            Some(Message::CompiledSize(size)) => {
+                let vm =
+                    VmAndConfig::from_str(&size.id.runtime).map_err(|error| line_error(&error))?;
+                if compiled_sizes.push(vm, size) {
+                    replaced += 1;
+                }
+                continue;
+            }
+            Some(Message::MemoryUsage(usage)) => {
+                let category = BenchCategory::from_str(&usage.id.category)
+                    .map_err(|error| line_error(&error))?;
+                let vm =
+                    VmAndConfig::from_str(&usage.id.runtime).map_err(|error| line_error(&error))?;
+                if memory_data.push(category, vm, usage) {
+                    replaced += 1;
+                }
+                continue;
+            }
+            Some(Message::RuntimeInfo(info)) => {
+                VmAndConfig::register(info);
+                continue;
+            }
+            Some(Message::PerfCounts(counts)) => {
+                let vm = VmAndConfig::from_str(&counts.id.runtime)
+                    .map_err(|error| line_error(&error))?;
+                if perf_data.push(vm, counts) {
+                    replaced += 1;
+                }
+                continue;
+            }
+            None => continue,
+        };
+        let Decoded {
+            category,
+            vm,
+            id,
+            typical,
+            throughput,
+        } = Decoded::new(measurement).map_err(|error| line_error(&error))?;
+        let position = groups
+            .iter()
+            .position(|group| group.category == category && group.name == id.case);
+        let group = match position {
+            Some(position) => &mut groups[position],
+            None => {
+                groups.push(BenchGroup {
+                    category,
+                    name: id.case,
+                    results: BTreeMap::new(),
+                    input: id.input,
+                    throughput,
+                });
+                groups.last_mut().expect("just pushed a group")
+            }
+        };
+        group.throughput = throughput;
+        if group.results.insert(vm, typical).is_some() {
+            replaced += 1;
+        }
+    }
+    if replaced != 0 {
+        eprintln!("merged duplicate runs: {replaced} results replaced by a later run");
+    }
+
+    let mut rendered = Rendered::default();
+    // The results of all groups, needed to plot the per-category geomeans and heatmaps.
+    let mut geomean_data: BTreeMap<BenchCategory, GeomeanData> = BTreeMap::new();
+    for bench_group in groups {
+        if !filters.keeps_case(&bench_group) {
+            continue;
+        }
+        rendered.plots.extend(plot_for_data(
+            ext_title,
+            style,
+            output,
+            filters,
+            &bench_group,
+        )?);
+        geomean_data
+            .entry(bench_group.category)
+            .or_default()
+            .push_group(filters, &bench_group);
+        rendered.groups.push(bench_group);
+    }
+    for (category, geomean_data) in &geomean_data {
+        rendered.geomeans.extend(geomean::plot_geomean(
+            ext_title,
+            style,
+            output,
+            geomean_options,
+            *category,
+            geomean_data,
+        )?);
+        rendered.heatmaps.extend(heatmap::plot_heatmap(
+            ext_title,
+            style,
+            output,
+            heatmap_baseline,
+            *category,
+            geomean_data,
+        )?);
+    }
+    rendered.memory = memory::plot_memory(
+        ext_title,
+        style,
+        output,
+        filters,
+        &rendered.groups,
+        &memory_data,
+    )?;
+    rendered.perf = perf::plot_perf(
+        ext_title,
+        style,
+        output,
+        filters,
+        &rendered.groups,
+        &perf_data,
+    )?;
+    rendered.compiled = sizes::plot_compiled_sizes(
+        ext_title,
+        style,
+        output,
+        filters,
+        &rendered.groups,
+        &modules,
+        &compiled_sizes,
+    )?;
+    rendered.startup_sizes = sizes::plot_startup_sizes(
+        ext_title,
+        style,
+        output,
+        filters,
+        &rendered.groups,
+        &modules,
+    )?;
+    Ok(rendered)
+}
+
+/// A benchmark result decoded from a single `benchmark-complete` message.
+struct Decoded {
+    category: BenchCategory,
+    vm: VmAndConfig,
+    id: BenchId,
+    typical: Estimate,
+    throughput: Option<Throughput>,
+}
+
+impl Decoded {
+    /// Resolves the category and runtime of the decoded `measurement`.
+    fn new(measurement: Measurement) -> Result<Self, Box<dyn Error>> {
+        let Measurement {
+            id,
+            typical,
+            throughput,
+        } = measurement;
+        Ok(Self {
+            category: BenchCategory::from_str(&id.category)?,
+            vm: VmAndConfig::from_str(&id.runtime)?,
+            id,
+            typical,
+            throughput,
+        })
+    }
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let args = Args::parse();
+    let filters = Filters {
+        filters: args.filters,
+        only: args.only,
+        exclude: args.exclude,
+        cases: args.cases,
+    };
+    let highlights = Highlights::new(args.highlight);
+    let style = Style {
+        scale: args.scale,
+        time: args.time,
+        highlights: &highlights,
+    };
+    let geomean_options = geomean::Options {
+        baseline: args.geomean_baseline,
+        weights: args.geomean_weights,
+        coverage: args.geomean_coverage,
+    };
+    let output = Output {
+        dir: args.out_dir,
+        format: args.format,
+        width: args.width,
+        font_scale: args.font_scale,
+        theme: args.theme,
+    };
+    if args.html && output.format != Format::Svg {
+        return Err("`--html` inlines the rendered plots and thus requires `--format svg`".into());
+    }
+    let mut rendered = match args.coremark.is_some() && io::stdin().is_terminal() {
+        // Only the CoreMark scores were requested.
+        true => Rendered::default(),
+        false => decode_stdin(
+            args.title.as_deref(),
+            style,
+            &output,
+            &filters,
+            &geomean_options,
+            &args.heatmap_baseline,
+        )?,
+    };
+    // Reads the scores after the results, whose `runtime-info` messages resolve the runtimes.
+    let scores = args
+        .coremark
+        .as_deref()
+        .map(coremark::read_scores)
+        .transpose()?;
+    if let Some(scores) = &scores {
+        rendered.coremark =
+            coremark::plot_coremark(args.title.as_deref(), style, &output, &filters, scores)?;
+    }
+    if args.html {
+        html::write_report(
+            args.title.as_deref(),
+            &output,
+            &filters,
+            &rendered,
+            scores.as_deref(),
+        )?;
+    }
+    Ok(())
+}
@@ -0,0 +1,124 @@
+//! Renders the peak memory usage of the runtimes per test case just like their times.
+
+use super::output::Output;
+use super::{
+    BenchCategory, BenchEntry, BenchGroup, Filters, Style, Time, Unit, VmAndConfig, plot_title,
+    render_plot,
+};
+use std::collections::BTreeMap;
+use std::error::Error;
+use wasmi_benchmarks::results::MemoryUsage;
+
+/// A measured quantity of the memory usage of a runtime.
+#[derive(Debug, Copy, Clone)]
+enum Metric {
+    /// The peak number of heap bytes allocated through the global allocator.
+    Heap,
+    /// The peak growth of the resident set size.
+    Rss,
+}
+
+impl Metric {
+    /// Returns the name of the metric as used in the file names of its plots.
+    fn name(self) -> &'static str {
+        match self {
+            Metric::Heap => "heap",
+            Metric::Rss => "rss",
+        }
+    }
+
+    /// Returns the description of the metric as used in the axis label of its plots.
+    fn kind(self, time: Time) -> &'static str {
+        match (self, time) {
+            (Metric::Heap, Time::Relative) => "Relative Peak Heap",
+            (Metric::Heap, Time::Absolute) => "Peak Heap",
+            (Metric::Rss, Time::Relative) => "Relative Peak RSS Growth",
+            (Metric::Rss, Time::Absolute) => "Peak RSS Growth",
+        }
+    }
+
+    /// Returns the measured value of the metric in bytes, if any.
+    fn value(self, usage: &MemoryUsage) -> Option<u64> {
+        match self {
+            Metric::Heap => Some(usage.heap_peak),
+            Metric::Rss => usage.rss_peak,
+        }
+    }
+}
+
+/// The memory usage of all runtimes by test case.
+#[derive(Debug, Default)]
+pub struct MemoryData {
+    usages: BTreeMap<(BenchCategory, String), BTreeMap<VmAndConfig, MemoryUsage>>,
+}
+
+impl MemoryData {
+    /// Records the memory `usage` of `vm`.
+    ///
+    /// Returns `true` if it replaces an earlier usage of the same benchmark.
+    pub fn push(&mut self, category: BenchCategory, vm: VmAndConfig, usage: MemoryUsage) -> bool {
+        let case = (category, usage.id.case.clone());
+        self.usages
+            .entry(case)
+            .or_default()
+            .insert(vm, usage)
+            .is_some()
+    }
+}
+
+/// Renders the memory usage of every test case of `groups` into `memory/{category}/{case}-heap.svg`
+/// and `memory/{category}/{case}-rss.svg` within the output directory.
+///
+/// Test cases without recorded memory usage, e.g. from `cargo criterion` output, are skipped.
+pub fn plot_memory(
+    ext_title: Option<&str>,
+    style: Style,
+    output: &Output,
+    filters: &Filters,
+    groups: &[BenchGroup],
+    data: &MemoryData,
+) -> Result<Vec<String>, Box<dyn Error>> {
+    let mut paths = Vec::new();
+    for group in groups {
+        let Some(usages) = data.usages.get(&(group.category, group.name.clone())) else {
+            continue;
+        };
+        for metric in [Metric::Heap, Metric::Rss] {
+            let entries: Vec<BenchEntry> = usages
+                .iter()
+                .filter(|&(&vm, _)| filters.keeps(vm))
+                .filter_map(|(&vm, usage)| {
+                    let bytes = metric.value(usage)?;
+                    Some(BenchEntry {
+                        vm,
+                        // Neither relative nor logarithmic plots can show zero bytes.
+                        time: (bytes as f64).max(1.0),
+                        unit: Unit::Bytes,
+                        coverage: None,
+                        throughput: None,
+                    })
+                })
+                .collect();
+            let Some(min) = entries
+                .iter()
+                .map(|entry| entry.time)
+                .min_by(f64::total_cmp)
+            else {
+                continue;
+            };
+            let (category, name, suffix) = (group.category, &group.name, metric.name());
+            let path = output.plot_path(&format!("memory/{category}/{name}-{suffix}"))?;
+            render_plot(
+                &plot_title(ext_title, &format!("memory/{suffix}/{category}/{name}")),
+                &path,
+                style,
+                output,
+                metric.kind(style.time),
+                min,
+                entries,
+            )?;
+            paths.push(path);
+        }
+    }
+    Ok(paths)
+}
@@ -0,0 +1,127 @@
+//! Where the plots are rendered to and how they look: file format, dimensions and theme.
+
+use plotters::prelude::*;
+use plotters::style::colors::full_palette as color;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// The file format of the rendered plots.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default, clap::ValueEnum)]
+pub enum Format {
+    /// Scalable vector graphics, e.g. for the README.
+    #[default]
+    Svg,
+    /// Portable network graphics, e.g. for slide decks and blog posts.
+    Png,
+}
+
+impl Format {
+    /// Returns the file extension of the format.
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Svg => "svg",
+            Self::Png => "png",
+        }
+    }
+}
+
+/// The color theme of the rendered plots.
+///
+/// Only affects the background, text and grid lines: runtimes keep their colors.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default, clap::ValueEnum)]
+pub enum Theme {
+    /// Dark text on a white background.
+    #[default]
+    Light,
+    /// Light text on a dark background.
+    Dark,
+}
+
+impl Theme {
+    /// The background of [`Theme::Dark`].
+    const DARK_BACKGROUND: RGBColor = RGBColor(22, 27, 34);
+    /// The text color of [`Theme::Dark`].
+    const DARK_FOREGROUND: RGBColor = RGBColor(230, 237, 243);
+
+    /// Returns the background color of the theme.
+    pub fn background(self) -> &'static RGBColor {
+        match self {
+            Self::Light => &color::WHITE,
+            Self::Dark => &Self::DARK_BACKGROUND,
+        }
+    }
+
+    /// Returns the color of texts, axes and grid lines of the theme.
+    pub fn foreground(self) -> &'static RGBColor {
+        match self {
+            Self::Light => &color::BLACK,
+            Self::Dark => &Self::DARK_FOREGROUND,
+        }
+    }
+}
+
+/// The output options shared by all rendered plots.
+#[derive(Debug, Clone)]
+pub struct Output {
+    /// The directory all plots are rendered into.
+    pub dir: PathBuf,
+    /// The file format of the plots.
+    pub format: Format,
+    /// The width of the plots in pixels.
+    pub width: u32,
+    /// The factor all font sizes, bar heights and margins are scaled by.
+    pub font_scale: f64,
+    /// The color theme of the plots.
+    pub theme: Theme,
+}
+
+impl Output {
+    /// The height of a single bar's row in pixels, before scaling.
+    const ROW_HEIGHT: u32 = 50;
+
+    /// Returns the path of the plot `name` within [`Output::dir`], creating its parent directories.
+    ///
+    /// The `name` is given without file extension, e.g. `execute/fibonacci-rec`.
+    pub fn plot_path(&self, name: &str) -> io::Result<String> {
+        let path = self.dir.join(format!("{name}.{}", self.format.extension()));
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        Ok(path.display().to_string())
+    }
+
+    /// Returns the path of the file `name` within [`Output::dir`].
+    pub fn file_path(&self, name: &str) -> PathBuf {
+        self.dir.join(name)
+    }
+
+    /// Returns the plot `name` for the plot at `path` as returned by [`Output::plot_path`].
+    pub fn plot_name<'a>(&self, path: &'a str) -> &'a str {
+        Path::new(path)
+            .strip_prefix(&self.dir)
+            .ok()
+            .and_then(Path::to_str)
+            .and_then(|name| name.strip_suffix(self.format.extension()))
+            .and_then(|name| name.strip_suffix('.'))
+            .unwrap_or(path)
+    }
+
+    /// Scales the font or layout size `px` by [`Output::font_scale`].
+    pub fn scaled(&self, px: u32) -> u32 {
+        (f64::from(px) * self.font_scale).round() as u32
+    }
+
+    /// Returns the dimensions of a horizontal bar chart with `bars` bars.
+    ///
+    /// Leaves room for the title, the axis labels and description as well as the margins.
+    pub fn bar_chart_size(&self, bars: usize) -> (u32, u32) {
+        let height = self.scaled(50 + 75 + 25 + 5) + bars as u32 * self.scaled(Self::ROW_HEIGHT);
+        (self.width, height)
+    }
+
+    /// Returns the text style of `family` at the scaled size `px` in the theme's text color.
+    pub fn text(&self, family: &'static str, px: u32) -> TextStyle<'static> {
+        TextStyle::from((family, self.scaled(px))).color(self.theme.foreground())
+    }
+}
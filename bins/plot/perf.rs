@@ -0,0 +1,118 @@
+//! Renders the hardware performance counters of the runtimes per execute test case.
+//!
+//! Instruction counts are far more stable than times on noisy machines and relate directly to
+//! the dispatch overhead of interpreters, e.g. as instructions per executed Wasm loop iteration.
+
+use super::output::Output;
+use super::{
+    BenchCategory, BenchEntry, BenchGroup, Filters, Style, Time, Unit, VmAndConfig, plot_title,
+    render_plot,
+};
+use std::collections::BTreeMap;
+use std::error::Error;
+use wasmi_benchmarks::results::{PerfCounts, ThroughputUnit};
+
+/// The hardware performance counters of all runtimes by execute test case.
+#[derive(Debug, Default)]
+pub struct PerfData {
+    counts: BTreeMap<String, BTreeMap<VmAndConfig, PerfCounts>>,
+}
+
+impl PerfData {
+    /// Records the hardware performance `counts` of `vm`.
+    ///
+    /// Returns `true` if they replace earlier counts of the same benchmark.
+    pub fn push(&mut self, vm: VmAndConfig, counts: PerfCounts) -> bool {
+        self.counts
+            .entry(counts.id.case.clone())
+            .or_default()
+            .insert(vm, counts)
+            .is_some()
+    }
+}
+
+/// Renders the retired instructions of every execute test case of `groups` into
+/// `perf/{case}-instructions.svg` within the output directory.
+///
+/// Counts are normalized per element for test cases declaring elements as their throughput,
+/// e.g. per loop iteration of the counter test cases, and per benchmark iteration otherwise.
+/// Also prints the instructions per cycle, branch misses and cache misses of every runtime.
+/// Test cases without recorded counters, e.g. from runs without `WASMI_BENCHMARKS_PERF`,
+/// are skipped.
+pub fn plot_perf(
+    ext_title: Option<&str>,
+    style: Style,
+    output: &Output,
+    filters: &Filters,
+    groups: &[BenchGroup],
+    data: &PerfData,
+) -> Result<Vec<String>, Box<dyn Error>> {
+    let mut paths = Vec::new();
+    for group in groups {
+        if group.category != BenchCategory::Execute {
+            continue;
+        }
+        let Some(counts) = data.counts.get(&group.name) else {
+            continue;
+        };
+        let (per, elements) = match group.throughput {
+            Some(throughput) if throughput.unit == ThroughputUnit::Elements => {
+                ("Op", throughput.per_iteration.max(1) as f64)
+            }
+            _ => ("Iteration", 1.0),
+        };
+        let entries: Vec<BenchEntry> = counts
+            .iter()
+            .filter(|&(&vm, _)| filters.keeps(vm))
+            .map(|(&vm, counts)| BenchEntry {
+                vm,
+                time: counts.instructions / elements,
+                unit: Unit::Count,
+                coverage: None,
+                throughput: None,
+            })
+            .collect();
+        let Some(min) = entries
+            .iter()
+            .map(|entry| entry.time)
+            .min_by(f64::total_cmp)
+        else {
+            continue;
+        };
+        let name = &group.name;
+        for (&vm, counts) in counts.iter().filter(|&(&vm, _)| filters.keeps(vm)) {
+            // Runtimes without counted cycles, e.g. from empty routines, have no meaningful IPC.
+            let ipc = match counts.cycles > 0.0 {
+                true => format!("{:.02}", counts.instructions / counts.cycles),
+                false => String::from("n/a"),
+            };
+            eprintln!(
+                "execute/{name}/perf: {}: {} instructions, {} cycles (IPC {ipc}), \
+                {} branch misses, {} cache misses per {}",
+                vm.label(),
+                Unit::Count.format(counts.instructions / elements),
+                Unit::Count.format(counts.cycles / elements),
+                Unit::Count.format(counts.branch_misses / elements),
+                Unit::Count.format(counts.cache_misses / elements),
+                per.to_lowercase(),
+            );
+        }
+        let kind = match style.time {
+            Time::Relative => format!("Relative Instructions per {per}"),
+            Time::Absolute => format!("Instructions per {per}"),
+        };
+        let path = output.plot_path(&format!("perf/{name}-instructions"))?;
+        render_plot(
+            &plot_title(ext_title, &format!("perf/instructions/{name}")),
+            &path,
+            style,
+            output,
+            &kind,
+            // Neither relative nor logarithmic plots can show zero instructions.
+            min.max(f64::MIN_POSITIVE),
+            entries,
+        )?;
+        paths.push(path);
+    }
+    Ok(paths)
+}
@@ -0,0 +1,127 @@
+//! Selecting Wasm runtimes and test cases by glob patterns.
+
+use super::{FromStrError, VmAndConfig};
+use plotters::style::RGBColor;
+use std::str::FromStr;
+
+/// A glob pattern, e.g. `wasmi-v2.*` or `*.unchecked`.
+///
+/// `*` matches any sequence of characters, `?` matches any single character.
+#[derive(Debug, Clone)]
+pub struct Glob {
+    pattern: String,
+}
+
+impl FromStr for Glob {
+    type Err = FromStrError;
+
+    fn from_str(pattern: &str) -> Result<Self, Self::Err> {
+        let pattern = pattern.trim();
+        if pattern.is_empty() {
+            return Err(FromStrError::from("invalid Glob: empty pattern"));
+        }
+        Ok(Self {
+            pattern: pattern.to_string(),
+        })
+    }
+}
+
+impl Glob {
+    /// Returns the pattern of the glob as given.
+    pub fn as_str(&self) -> &str {
+        &self.pattern
+    }
+
+    /// Returns `true` if the glob matches all of `text`.
+    pub fn matches(&self, text: &str) -> bool {
+        let pattern: Vec<char> = self.pattern.chars().collect();
+        let text: Vec<char> = text.chars().collect();
+        let (mut p, mut t) = (0, 0);
+        // The position of the last `*` and of the text it currently extends to.
+        let mut backtrack = None;
+        while t < text.len() {
+            match pattern.get(p) {
+                Some('*') => {
+                    backtrack = Some((p, t));
+                    p += 1;
+                }
+                Some(&c) if c == '?' || c == text[t] => {
+                    p += 1;
+                    t += 1;
+                }
+                _ => match backtrack {
+                    // Let the last `*` match one more character and retry.
+                    Some((star, start)) => {
+                        p = star + 1;
+                        t = start + 1;
+                        backtrack = Some((star, start + 1));
+                    }
+                    None => return false,
+                },
+            }
+        }
+        pattern[p..].iter().all(|&c| c == '*')
+    }
+
+    /// Returns `true` if the glob selects `vm`.
+    ///
+    /// Matches either the full ID or just the Wasm runtime, so `wasmi-v2` selects all of
+    /// `wasmi-v2.eager.checked`, `wasmi-v2.lazy.checked` and so on.
+    pub fn selects(&self, vm: VmAndConfig) -> bool {
+        self.matches(vm.id()) || self.matches(vm.runtime())
+    }
+}
+
+/// The Wasm runtimes highlighted in the rendered plots.
+///
+/// Every selector has its own color, so several runtimes can be compared at a glance.
+#[derive(Debug, Clone, Default)]
+pub struct Highlights {
+    selectors: Vec<Glob>,
+}
+
+impl Highlights {
+    /// The colors of the highlighted runtimes in order of their selectors.
+    ///
+    /// Starts with orange as used for a single highlighted runtime and continues with colors
+    /// that stand out from the blue JITs and teal interpreters. Cycles for more selectors.
+    const COLORS: [RGBColor; 5] = [
+        VmAndConfig::ORANGE,
+        RGBColor(214, 39, 40),
+        RGBColor(148, 103, 189),
+        RGBColor(227, 119, 194),
+        RGBColor(140, 86, 75),
+    ];
+
+    /// Creates the [`Highlights`] of `selectors`, where `none` disables highlighting.
+    pub fn new(selectors: Vec<Glob>) -> Self {
+        let selectors = selectors
+            .into_iter()
+            .filter(|selector| selector.as_str() != "none")
+            .collect();
+        Self { selectors }
+    }
+
+    /// Returns the highlight color of `vm` if any selector selects it.
+    ///
+    /// The first selector that selects `vm` determines its color.
+    pub fn color(&self, vm: VmAndConfig) -> Option<RGBColor> {
+        self.selectors
+            .iter()
+            .position(|selector| selector.selects(vm))
+            .map(|index| Self::COLORS[index % Self::COLORS.len()])
+    }
+
+    /// Returns `true` if the plots need a legend to tell the highlight colors apart.
+    pub fn needs_legend(&self) -> bool {
+        self.selectors.len() > 1
+    }
+
+    /// Returns every selector with its highlight color.
+    pub fn legend(&self) -> impl Iterator<Item = (&str, RGBColor)> {
+        self.selectors
+            .iter()
+            .zip(Self::COLORS.iter().cycle())
+            .map(|(selector, color)| (selector.as_str(), *color))
+    }
+}
@@ -0,0 +1,331 @@
+//! Relates the startup times of the runtimes and the sizes of their compiled artifacts to the
+//! sizes of the instantiated Wasm modules.
+
+use super::output::{Format, Output};
+use super::{
+    BenchCategory, BenchEntry, BenchGroup, Filters, Style, Time, Unit, VmAndConfig, plot_title,
+    render_plot,
+};
+use plotters::coord::Shift;
+use plotters::prelude::*;
+use plotters::style::text_anchor::{HPos, Pos, VPos};
+use std::collections::BTreeMap;
+use std::error::Error;
+use wasmi_benchmarks::results::{
+    CompiledSize, ModuleInfo, Throughput, ThroughputUnit, format_bytes, format_duration_ns,
+};
+
+/// The sizes of the artifacts the runtimes compile from the startup modules.
+#[derive(Debug, Default)]
+pub struct CompiledSizes {
+    /// The compiled sizes in bytes by runtime and benchmark group, e.g. `startup/bz2`.
+    sizes: BTreeMap<String, BTreeMap<VmAndConfig, u64>>,
+}
+
+impl CompiledSizes {
+    /// Records the compiled `size` of `vm`.
+    ///
+    /// Returns `true` if it replaces an earlier size of the same benchmark.
+    pub fn push(&mut self, vm: VmAndConfig, size: CompiledSize) -> bool {
+        self.sizes
+            .entry(size.id.group())
+            .or_default()
+            .insert(vm, size.compiled_size)
+            .is_some()
+    }
+}
+
+/// Renders the compiled size of every runtime relative to the size of the instantiated module into
+/// `compiled/{case}.svg` within the output directory for every startup test case of `groups`.
+///
+/// Also prints the compiled and module sizes, so they can be compared in absolute terms.
+/// Test cases without recorded module or compiled sizes, e.g. from `cargo criterion` output,
+/// are skipped.
+pub fn plot_compiled_sizes(
+    ext_title: Option<&str>,
+    style: Style,
+    output: &Output,
+    filters: &Filters,
+    groups: &[BenchGroup],
+    modules: &BTreeMap<String, ModuleInfo>,
+    compiled: &CompiledSizes,
+) -> Result<Vec<String>, Box<dyn Error>> {
+    let mut paths = Vec::new();
+    for group in groups {
+        let id = format!("{}/{}", group.category, group.name);
+        let (Some(module), Some(sizes)) = (modules.get(&id), compiled.sizes.get(&id)) else {
+            continue;
+        };
+        let module_size = module.module_size as f64;
+        let data: Vec<BenchEntry> = sizes
+            .iter()
+            .filter(|&(&vm, _)| filters.keeps(vm))
+            .map(|(&vm, &size)| BenchEntry {
+                vm,
+                time: size as f64,
+                unit: Unit::Bytes,
+                coverage: None,
+                throughput: None,
+            })
+            .collect();
+        if data.is_empty() {
+            continue;
+        }
+        for entry in &data {
+            eprintln!(
+                "{id}/compiled: {}: {} compiled from {} (x{:.02})",
+                entry.vm.label(),
+                format_bytes(entry.time),
+                format_bytes(module_size),
+                entry.time / module_size,
+            );
+        }
+        let path = output.plot_path(&format!("compiled/{}", group.name))?;
+        render_plot(
+            &plot_title(ext_title, &format!("compiled/{}", group.name)),
+            &path,
+            // Compiled sizes are always plotted relative to the size of the module.
+            Style {
+                time: Time::Relative,
+                ..style
+            },
+            output,
+            "Compiled Size vs. .wasm",
+            module_size,
+            data,
+        )?;
+        paths.push(path);
+    }
+    Ok(paths)
+}
+
+/// The startup times of a single runtime by the size of the instantiated module.
+struct Series {
+    /// The runtime.
+    vm: VmAndConfig,
+    /// The module size in bytes and the startup time in nanoseconds, by ascending size.
+    points: Vec<(f64, f64)>,
+}
+
+/// Returns the size of the module of every startup test case and what the size measures.
+///
+/// Prefers the module sizes written by the benchmark harness, see [`ModuleInfo`], and falls back
+/// to the code sizes that the startup test cases declare as their throughput.
+fn module_sizes<'a>(
+    groups: &[&'a BenchGroup],
+    modules: &BTreeMap<String, ModuleInfo>,
+) -> Option<(&'static str, Vec<(&'a BenchGroup, f64)>)> {
+    let module_sizes: Option<Vec<_>> = groups
+        .iter()
+        .map(|group| {
+            let module = modules.get(&format!("{}/{}", group.category, group.name))?;
+            Some((*group, module.module_size as f64))
+        })
+        .collect();
+    if let Some(module_sizes) = module_sizes {
+        return Some(("Module size", module_sizes));
+    }
+    let code_sizes: Option<Vec<_>> = groups
+        .iter()
+        .map(|group| match group.throughput? {
+            Throughput {
+                per_iteration,
+                unit: ThroughputUnit::Bytes,
+            } => Some((*group, per_iteration as f64)),
+            _ => None,
+        })
+        .collect();
+    code_sizes.map(|code_sizes| ("Code section size", code_sizes))
+}
+
+/// Prints the startup throughput of every runtime in code bytes per second.
+///
+/// Aggregates all startup test cases a runtime ran as their total code size over their total
+/// startup time, so the largest modules dominate just like they dominate real startup costs.
+fn print_throughputs(groups: &[&BenchGroup], filters: &Filters) {
+    let mut totals: BTreeMap<VmAndConfig, (u64, f64)> = BTreeMap::new();
+    for group in groups {
+        let Some(throughput) = group.throughput else {
+            continue;
+        };
+        for entry in group.entries(filters) {
+            let total = totals.entry(entry.vm).or_default();
+            total.0 += throughput.per_iteration;
+            total.1 += entry.time;
+        }
+    }
+    let mut totals: Vec<_> = totals.into_iter().collect();
+    // Highest throughput first.
+    totals.sort_by(|(_, lhs), (_, rhs)| (rhs.0 as f64 / rhs.1).total_cmp(&(lhs.0 as f64 / lhs.1)));
+    for (vm, (bytes, time)) in totals {
+        let throughput = Throughput {
+            per_iteration: bytes,
+            unit: ThroughputUnit::Bytes,
+        };
+        eprintln!(
+            "startup/throughput: {}: {} of code",
+            vm.label(),
+            throughput.format_rate(time)
+        );
+    }
+}
+
+/// Renders the startup times of all runtimes by module size into `startup-size.svg` within the
+/// output directory and prints the startup throughput of every runtime.
+///
+/// Both axes are logarithmic, so runtimes whose startup time scales linearly with the module size
+/// form parallel lines and outliers for specific modules stand out.
+pub fn plot_startup_sizes(
+    ext_title: Option<&str>,
+    style: Style,
+    output: &Output,
+    filters: &Filters,
+    groups: &[BenchGroup],
+    modules: &BTreeMap<String, ModuleInfo>,
+) -> Result<Option<String>, Box<dyn Error>> {
+    let groups: Vec<&BenchGroup> = groups
+        .iter()
+        .filter(|group| group.category == BenchCategory::Startup)
+        .collect();
+    if groups.is_empty() {
+        return Ok(None);
+    }
+    print_throughputs(&groups, filters);
+    let Some((x_desc, sizes)) = module_sizes(&groups, modules) else {
+        eprintln!("startup/size: missing module sizes: skipping module size plot");
+        return Ok(None);
+    };
+    let mut series: BTreeMap<VmAndConfig, Vec<(f64, f64)>> = BTreeMap::new();
+    for (group, size) in &sizes {
+        for entry in group.entries(filters) {
+            series
+                .entry(entry.vm)
+                .or_default()
+                .push((*size, entry.time));
+        }
+    }
+    let mut series: Vec<Series> = series
+        .into_iter()
+        .map(|(vm, mut points)| {
+            points.sort_by(|lhs, rhs| lhs.0.total_cmp(&rhs.0));
+            Series { vm, points }
+        })
+        .collect();
+    if series.is_empty() {
+        return Ok(None);
+    }
+    // Highlighted runtimes last so they are drawn on top of all others.
+    series.sort_by_key(|series| style.highlights.color(series.vm).is_some());
+    let path = output.plot_path("startup-size")?;
+    let title = plot_title(ext_title, "startup/size");
+    let size = (output.width, output.width * 3 / 4);
+    match output.format {
+        Format::Svg => {
+            let root = SVGBackend::new(&path, size).into_drawing_area();
+            draw_sizes(root, &title, x_desc, style, output, &series)?;
+        }
+        Format::Png => {
+            let root = BitMapBackend::new(&path, size).into_drawing_area();
+            draw_sizes(root, &title, x_desc, style, output, &series)?;
+        }
+    }
+    Ok(Some(path))
+}
+
+/// Draws the startup time `series` of all runtimes onto `root` as a log-log scatter plot.
+fn draw_sizes<DB>(
+    root: DrawingArea<DB, Shift>,
+    title: &str,
+    x_desc: &str,
+    style: Style,
+    output: &Output,
+    series: &[Series],
+) -> Result<(), Box<dyn Error>>
+where
+    DB: DrawingBackend,
+    DB::ErrorType: 'static,
+{
+    let points = || series.iter().flat_map(|series| series.points.iter());
+    let (min_x, max_x) = bounds(points().map(|point| point.0));
+    let (min_y, max_y) = bounds(points().map(|point| point.1));
+    root.fill(output.theme.background())?;
+    let root = root.margin(5, 5, 5, 5).titled(
+        title,
+        output
+            .text("monospace", 45)
+            .pos(Pos::new(HPos::Center, VPos::Center)),
+    )?;
+    let mut chart = ChartBuilder::on(&root)
+        .x_label_area_size(output.scaled(75))
+        .y_label_area_size(output.scaled(150))
+        .margin_right(output.scaled(50))
+        .margin_top(output.scaled(25))
+        .build_cartesian_2d(
+            (min_x * 0.8..max_x * 1.25).log_scale(),
+            (min_y * 0.5..max_y * 2.0).log_scale(),
+        )?;
+    let foreground = output.theme.foreground();
+    chart
+        .configure_mesh()
+        .axis_style(foreground)
+        .bold_line_style(foreground.mix(0.15))
+        .light_line_style(foreground.mix(0.05))
+        .x_desc(format!("{x_desc} (logarithmic scale)"))
+        .y_desc("Startup time (lower is better)")
+        .x_label_formatter(&|bytes| format_bytes(*bytes))
+        .y_label_formatter(&|ns| format_duration_ns(*ns))
+        .x_label_style(output.text("sans-serif", 20))
+        .y_label_style(output.text("sans-serif", 20))
+        .axis_desc_style(output.text("sans-serif", 35))
+        .draw()?;
+    let radius = output.scaled(5);
+    let width = output.scaled(2);
+    let mut labelled = false;
+    for series in series {
+        let highlight = style.highlights.color(series.vm);
+        let color = series.vm.color(style.highlights);
+        // Only the highlighted runtimes stand out enough to be told apart by a legend.
+        let line_color = match highlight {
+            Some(_) => color.stroke_width(width * 2),
+            None => color.mix(0.5).stroke_width(width),
+        };
+        let line = chart.draw_series(LineSeries::new(series.points.iter().copied(), line_color))?;
+        if highlight.is_some() {
+            labelled = true;
+            let size = output.scaled(10) as i32;
+            line.label(series.vm.label()).legend(move |(x, y)| {
+                Rectangle::new(
+                    [(x, y - size / 2), (x + 2 * size, y + size / 2)],
+                    color.filled(),
+                )
+            });
+        }
+        chart.draw_series(
+            series
+                .points
+                .iter()
+                .map(|&point| Circle::new(point, radius, color.filled())),
+        )?;
+    }
+    if labelled {
+        chart
+            .configure_series_labels()
+            .position(SeriesLabelPosition::UpperLeft)
+            .margin(output.scaled(15))
+            .legend_area_size(output.scaled(30))
+            .label_font(output.text("sans-serif", 24))
+            .background_style(output.theme.background().mix(0.9))
+            .border_style(output.theme.foreground().mix(0.3))
+            .draw()?;
+    }
+    // To avoid the IO failure being ignored silently, we manually call the present function
+    root.present()?;
+    Ok(())
+}
+
+/// Returns the minimum and maximum of the non-empty `values`.
+fn bounds(values: impl Iterator<Item = f64>) -> (f64, f64) {
+    values.fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), value| {
+        (min.min(value), max.max(value))
+    })
+}
@@ -0,0 +1,199 @@
+//! The Wasm runtimes found in the benchmark results and their metadata.
+
+use super::FromStrError;
+use super::select::Highlights;
+use benchmark_utils::RuntimeKind;
+use plotters::style::RGBColor;
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::str::FromStr;
+use std::sync::Mutex;
+use wasmi_benchmarks::results::RuntimeInfo;
+
+/// The metadata of a Wasm runtime with its configuration.
+#[derive(Debug)]
+struct Metadata {
+    /// The unique ID, e.g. `wasmi-v2.eager.checked`.
+    id: String,
+    /// The human readable name, e.g. `Wasmi v2 (eager)`.
+    label: String,
+    /// The execution kind, if known.
+    kind: Option<RuntimeKind>,
+    /// Whether a newer version of the Wasm runtime is benchmarked as well.
+    is_outdated: bool,
+    /// The position of its `runtime-info` message within the results, if known.
+    order: Option<usize>,
+}
+
+/// VM under test and its configuration, identified by `{runtime}.{config...}`.
+///
+/// For example `wasmi-v2.eager.checked` or `wasm3.lazy`.
+///
+/// The metadata of the Wasm runtimes is taken from the `runtime-info` messages the benchmarks
+/// write with their results, see [`VmAndConfig::register`], since results are usually plotted by a
+/// build with other features than the one that produced them. Runtimes without such a message,
+/// e.g. from results of `cargo criterion`, are still plotted with a label derived from their ID
+/// but without a kind.
+///
+/// Cheap to copy since the metadata of every runtime is interned for the rest of the program.
+#[derive(Debug, Copy, Clone)]
+pub struct VmAndConfig(&'static Metadata);
+
+/// The interned metadata of all Wasm runtimes seen so far by their ID.
+static REGISTRY: Mutex<BTreeMap<String, &'static Metadata>> = Mutex::new(BTreeMap::new());
+
+/// Splits `id` into its Wasm runtime and its configuration, e.g. `wasmi-v2` and `eager.checked`.
+///
+/// Dots followed by a digit belong to the runtime's version, e.g. in `wasmi-v0.31`.
+fn split_id(id: &str) -> (&str, Option<&str>) {
+    let split = id.match_indices('.').find(|(index, _)| {
+        !id[index + 1..]
+            .chars()
+            .next()
+            .is_some_and(|c| c.is_ascii_digit())
+    });
+    match split {
+        Some((index, _)) => (&id[..index], Some(&id[index + 1..])),
+        None => (id, None),
+    }
+}
+
+impl VmAndConfig {
+    /// Registers the Wasm runtime described by a `runtime-info` message of the results.
+    ///
+    /// Runtimes are ordered as registered, i.e. as benchmarked. Later messages of an already
+    /// registered runtime, e.g. of concatenated results, are ignored.
+    pub fn register(info: RuntimeInfo) {
+        let mut registry = REGISTRY.lock().unwrap_or_else(|error| error.into_inner());
+        let order = registry.values().filter(|vm| vm.order.is_some()).count();
+        registry.entry(info.id.clone()).or_insert_with(|| {
+            let metadata = Metadata {
+                id: info.id,
+                label: info.label,
+                kind: Some(info.kind),
+                is_outdated: info.is_outdated,
+                order: Some(order),
+            };
+            &*Box::leak(Box::new(metadata))
+        });
+    }
+
+    /// Returns the known Wasm runtime with the `id`, if any.
+    pub fn lookup(id: &str) -> Option<Self> {
+        let registry = REGISTRY.lock().unwrap_or_else(|error| error.into_inner());
+        registry
+            .get(id)
+            .copied()
+            .filter(|metadata| metadata.order.is_some())
+            .map(Self)
+    }
+
+    /// Returns the unique ID of the Wasm runtime with its configuration.
+    pub fn id(&self) -> &'static str {
+        &self.0.id
+    }
+
+    /// Returns the Wasm runtime without its configuration, e.g. `wasmi-v2`.
+    pub fn runtime(&self) -> &'static str {
+        split_id(self.id()).0
+    }
+
+    /// Returns the label of the Wasm runtime kind.
+    pub fn label(&self) -> &'static str {
+        &self.0.label
+    }
+
+    /// The color of JIT-compiling Wasm runtimes.
+    pub const BLUE: RGBColor = RGBColor(52, 119, 186);
+    /// The color of most interpreter-based Wasm runtimes.
+    pub const TEAL: RGBColor = RGBColor(76, 161, 143);
+    /// The color of the (first) highlighted Wasm runtime.
+    pub const ORANGE: RGBColor = RGBColor(227, 146, 63);
+    /// The color of Wasm runtimes of unknown kind.
+    pub const GRAY: RGBColor = RGBColor(150, 150, 150);
+
+    /// Returns the color associated to the Wasm runtime.
+    ///
+    /// Runtimes selected by `highlights` have their highlight color, all others are colored
+    /// by their [`RuntimeKind`]: JITs are blue, interpreters are teal and unknown ones are gray.
+    pub fn color(&self, highlights: &Highlights) -> RGBColor {
+        if let Some(color) = highlights.color(*self) {
+            return color;
+        }
+        match self.kind() {
+            Some(RuntimeKind::Jit) => Self::BLUE,
+            Some(RuntimeKind::Interpreter) => Self::TEAL,
+            None => Self::GRAY,
+        }
+    }
+
+    /// Returns the execution kind of the Wasm runtime, if known.
+    pub fn kind(&self) -> Option<RuntimeKind> {
+        self.0.kind
+    }
+
+    /// Returns `true` if a newer version of this Wasm runtime is supported.
+    pub fn is_outdated(&self) -> bool {
+        self.0.is_outdated
+    }
+}
+
+impl FromStr for VmAndConfig {
+    type Err = FromStrError;
+
+    /// Returns the Wasm runtime with the given `id`.
+    ///
+    /// Unknown Wasm runtimes are labelled after their ID, e.g. `foo (bar, baz)` for
+    /// `foo.bar.baz`, and are of unknown kind.
+    fn from_str(id: &str) -> Result<Self, Self::Err> {
+        if id.is_empty() {
+            return Err(FromStrError::from("invalid VmAndConfig: empty ID"));
+        }
+        let mut registry = REGISTRY.lock().unwrap_or_else(|error| error.into_inner());
+        if let Some(metadata) = registry.get(id) {
+            return Ok(Self(metadata));
+        }
+        let label = match split_id(id) {
+            (runtime, Some(config)) => {
+                let config: Vec<&str> = config.split('.').collect();
+                format!("{runtime} ({})", config.join(", "))
+            }
+            (runtime, None) => runtime.to_string(),
+        };
+        eprintln!(
+            "unknown Wasm runtime {id:?} without `runtime-info`: labelled {label:?} and of unknown kind"
+        );
+        let metadata = Metadata {
+            id: id.to_string(),
+            label,
+            kind: None,
+            is_outdated: false,
+            order: None,
+        };
+        let metadata = &*Box::leak(Box::new(metadata));
+        registry.insert(id.to_string(), metadata);
+        Ok(Self(metadata))
+    }
+}
+
+impl PartialEq for VmAndConfig {
+    fn eq(&self, other: &Self) -> bool {
+        self.id() == other.id()
+    }
+}
+
+impl Eq for VmAndConfig {}
+
+impl PartialOrd for VmAndConfig {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for VmAndConfig {
+    /// Orders known Wasm runtimes as benchmarked, followed by all unknown ones by ID.
+    fn cmp(&self, other: &Self) -> Ordering {
+        let key = |vm: &Self| (vm.0.order.is_none(), vm.0.order, vm.id());
+        key(self).cmp(&key(other))
+    }
+}
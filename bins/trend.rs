@@ -0,0 +1,436 @@
+use clap::{Parser, Subcommand};
+use plotters::prelude::*;
+use plotters::style::colors::full_palette as color;
+use serde_json as json;
+use std::collections::{BTreeMap, BTreeSet};
+use std::error::Error;
+use std::fs;
+use std::io::{self, BufRead, Write as _};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::{SystemTime, UNIX_EPOCH};
+use wasmi_benchmarks::results::{BenchId, DecodeError, Estimate, Measurement, format_duration_ns};
+
+/// Records benchmark runs into an append-only history and plots their trends over time.
+#[derive(Debug, Parser)]
+struct Args {
+    #[command(subcommand)]
+    mode: Mode,
+}
+
+#[derive(Debug, Subcommand)]
+enum Mode {
+    /// Appends the Criterion benchmark results (read as JSON from stdin) as a new run.
+    Record {
+        /// The machine the benchmarks ran on, e.g. `apple-m2-pro`.
+        #[arg(long)]
+        machine: String,
+        /// The history file; defaults to `data/{machine}/history.jsonl`.
+        #[arg(long)]
+        store: Option<PathBuf>,
+        /// The benchmarked git revision; defaults to `git describe --always --dirty`.
+        #[arg(long)]
+        revision: Option<String>,
+    },
+    /// Renders the time of every runtime per test case over all recorded runs.
+    Plot {
+        /// The machine whose history is plotted, e.g. `apple-m2-pro`.
+        #[arg(long)]
+        machine: String,
+        /// The history file; defaults to `data/{machine}/history.jsonl`.
+        #[arg(long)]
+        store: Option<PathBuf>,
+        /// Only plots the given test cases, e.g. `execute/fibonacci-rec`.
+        ///
+        /// May be given repeatedly or as a comma separated list.
+        #[arg(long = "case", value_delimiter = ',')]
+        cases: Vec<String>,
+    },
+}
+
+/// A single recorded benchmark run.
+#[derive(Debug)]
+struct Run {
+    /// Seconds since the Unix epoch at which the run was recorded.
+    timestamp: u64,
+    /// The benchmarked git revision.
+    revision: String,
+    /// The machine the benchmarks ran on.
+    machine: String,
+    /// The versions of the Wasm runtimes' crates per runtime adapter crate.
+    runtimes: BTreeMap<String, BTreeMap<String, String>>,
+    /// The typical time of every benchmark of the run.
+    results: BTreeMap<BenchId, Estimate>,
+}
+
+impl Run {
+    /// Encodes `self` as a single JSON line of the history file.
+    fn encode(&self) -> json::Value {
+        let results: Vec<_> = self
+            .results
+            .iter()
+            .map(|(id, typical)| {
+                json::json!({
+                    "id": id.to_string(),
+                    "estimate": typical.estimate,
+                    "lower_bound": typical.lower_bound,
+                    "upper_bound": typical.upper_bound,
+                })
+            })
+            .collect();
+        json::json!({
+            "timestamp": self.timestamp,
+            "revision": self.revision,
+            "machine": self.machine,
+            "runtimes": self.runtimes,
+            "results": results,
+        })
+    }
+
+    /// Decodes a single JSON line of the history file.
+    fn decode(line: &str) -> Result<Self, Box<dyn Error>> {
+        let run: json::Value = json::from_str(line)?;
+        let str_field = |name: &str| {
+            run.get(name)
+                .and_then(json::Value::as_str)
+                .map(String::from)
+                .ok_or_else(|| DecodeError::from(format!("malformed `{name}` value")))
+        };
+        let Some(timestamp) = run.get("timestamp").and_then(json::Value::as_u64) else {
+            return Err(DecodeError::from("malformed `timestamp` value").into());
+        };
+        let runtimes = match run.get("runtimes") {
+            Some(runtimes) => json::from_value(runtimes.clone())?,
+            None => BTreeMap::new(),
+        };
+        let Some(entries) = run.get("results").and_then(json::Value::as_array) else {
+            return Err(DecodeError::from("malformed `results` value").into());
+        };
+        let mut results = BTreeMap::new();
+        for entry in entries {
+            let field = |name: &str| {
+                entry
+                    .get(name)
+                    .and_then(json::Value::as_f64)
+                    .ok_or_else(|| DecodeError::from(format!("malformed result `{name}`")))
+            };
+            let Some(id) = entry.get("id").and_then(json::Value::as_str) else {
+                return Err(DecodeError::from("malformed result `id`").into());
+            };
+            let typical = Estimate {
+                estimate: field("estimate")?,
+                lower_bound: field("lower_bound")?,
+                upper_bound: field("upper_bound")?,
+            };
+            results.insert(id.parse()?, typical);
+        }
+        Ok(Self {
+            timestamp,
+            revision: str_field("revision")?,
+            machine: str_field("machine")?,
+            runtimes,
+            results,
+        })
+    }
+}
+
+/// Returns the history file of `machine` unless `store` overrides it.
+fn store_path(machine: &str, store: Option<PathBuf>) -> PathBuf {
+    store.unwrap_or_else(|| Path::new("data").join(machine).join("history.jsonl"))
+}
+
+/// Returns the output of `git describe --always --dirty` for the current checkout.
+fn git_revision() -> Result<String, Box<dyn Error>> {
+    let output = Command::new("git")
+        .args(["describe", "--always", "--dirty"])
+        .output()?;
+    if !output.status.success() {
+        return Err(
+            DecodeError::from("failed to query the git revision: pass `--revision`").into(),
+        );
+    }
+    Ok(String::from_utf8(output.stdout)?.trim().to_string())
+}
+
+/// Returns the versions of the dependencies of every Wasm runtime adapter crate.
+///
+/// The adapter crates are all named `rt-*` and each depends on its Wasm runtime's crates next to
+/// the shared `benchmark-utils` and `anyhow` crates, which are skipped. The versions are read from
+/// the `Cargo.lock` file so they match what was actually benchmarked.
+fn runtime_versions(lockfile: &str) -> BTreeMap<String, BTreeMap<String, String>> {
+    // Each `[[package]]` of the lockfile with its `name`, `version` and `dependencies`.
+    let mut packages: Vec<(String, String, Vec<String>)> = Vec::new();
+    let mut in_dependencies = false;
+    for line in lockfile.lines().map(str::trim) {
+        let unquote = |value: &str| value.trim().trim_matches('"').to_string();
+        if line == "[[package]]" {
+            packages.push(Default::default());
+            in_dependencies = false;
+        } else if let Some(package) = packages.last_mut() {
+            if let Some(name) = line.strip_prefix("name = ") {
+                package.0 = unquote(name);
+            } else if let Some(version) = line.strip_prefix("version = ") {
+                package.1 = unquote(version);
+            } else if line.starts_with("dependencies = [") {
+                in_dependencies = true;
+            } else if in_dependencies && line == "]" {
+                in_dependencies = false;
+            } else if in_dependencies {
+                package.2.push(unquote(line.trim_end_matches(',')));
+            }
+        }
+    }
+    let version_of = |dependency: &str| match dependency.split_whitespace().collect::<Vec<_>>()[..]
+    {
+        // Dependencies with multiple versions in the lockfile carry their version (and source).
+        [name, version, ..] => (name.to_string(), version.to_string()),
+        _ => {
+            let version = packages
+                .iter()
+                .find(|(name, _, _)| name == dependency)
+                .map(|(_, version, _)| version.clone())
+                .unwrap_or_default();
+            (dependency.to_string(), version)
+        }
+    };
+    packages
+        .iter()
+        .filter(|(name, _, _)| name.starts_with("rt-"))
+        .map(|(name, _, dependencies)| {
+            let versions = dependencies
+                .iter()
+                .map(|dependency| version_of(dependency))
+                .filter(|(name, _)| !matches!(&name[..], "benchmark-utils" | "anyhow"))
+                .collect();
+            (name.clone(), versions)
+        })
+        .collect()
+}
+
+fn record(
+    machine: String,
+    store: Option<PathBuf>,
+    revision: Option<String>,
+) -> Result<(), Box<dyn Error>> {
+    let mut results = BTreeMap::new();
+    for line in io::stdin().lock().lines() {
+        if let Some(measurement) = Measurement::decode(&line?)? {
+            results.insert(measurement.id, measurement.typical);
+        }
+    }
+    if results.is_empty() {
+        return Err(DecodeError::from("no benchmark results found in the input").into());
+    }
+    let revision = match revision {
+        Some(revision) => revision,
+        None => git_revision()?,
+    };
+    let timestamp = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+    let runtimes = runtime_versions(&fs::read_to_string("Cargo.lock").unwrap_or_default());
+    let run = Run {
+        timestamp,
+        revision,
+        machine: machine.clone(),
+        runtimes,
+        results,
+    };
+    let path = store_path(&machine, store);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut file = fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)?;
+    writeln!(file, "{}", run.encode())?;
+    eprintln!(
+        "recorded {} results of revision {} into {}",
+        run.results.len(),
+        run.revision,
+        path.display()
+    );
+    Ok(())
+}
+
+/// Shortens a full commit hash at the start of `revision` to 7 digits for the plot labels.
+///
+/// Keeps the rest as is, e.g. the `-dirty` suffix, so that dirty and clean runs of the same commit
+/// remain distinguishable, and leaves `git describe` output like `v0.1.0-3-g1234567` untouched.
+fn short_revision(revision: &str) -> String {
+    let (hash, suffix) = revision.split_at(revision.find('-').unwrap_or(revision.len()));
+    if hash.len() == 40 && hash.chars().all(|c| c.is_ascii_hexdigit()) {
+        return format!("{}{suffix}", &hash[..7]);
+    }
+    revision.to_string()
+}
+
+/// The times of a single runtime across all runs, indexed by the position of the run.
+type Series = Vec<(usize, f64)>;
+
+fn plot(machine: String, store: Option<PathBuf>, cases: Vec<String>) -> Result<(), Box<dyn Error>> {
+    let path = store_path(&machine, store);
+    let mut runs = Vec::new();
+    for (index, line) in fs::read_to_string(&path)?.lines().enumerate() {
+        // The store is append-only and may be edited by hand, so tolerate blank lines.
+        if line.trim().is_empty() {
+            continue;
+        }
+        let run = Run::decode(line)
+            .map_err(|error| format!("{}:{}: {error}", path.display(), index + 1))?;
+        if run.machine == machine {
+            runs.push(run);
+        }
+    }
+    runs.sort_by_key(|run| run.timestamp);
+    let revisions: Vec<String> = runs
+        .iter()
+        .map(|run| short_revision(&run.revision))
+        .collect();
+    // Group the times by test case and then by runtime, in order of the runs.
+    let mut groups: BTreeMap<String, BTreeMap<String, Series>> = BTreeMap::new();
+    for (index, run) in runs.iter().enumerate() {
+        for (id, typical) in &run.results {
+            let group = id.group();
+            if !cases.is_empty() && !cases.contains(&group) {
+                continue;
+            }
+            let runtime = match &id.input {
+                Some(input) => format!("{}/{input}", id.runtime),
+                None => id.runtime.clone(),
+            };
+            groups
+                .entry(group)
+                .or_default()
+                .entry(runtime)
+                .or_default()
+                .push((index, typical.estimate));
+        }
+    }
+    report_version_changes(&runs);
+    for (group, series) in &groups {
+        render_trend(
+            &format!("{group} - {machine}"),
+            &format!("target/wasmi-benchmarks/trend/{group}.svg"),
+            &revisions,
+            series,
+        )?;
+    }
+    Ok(())
+}
+
+/// Prints the Wasm runtime crates whose version changed between two consecutive runs.
+///
+/// This is where to look for the cause of a step in one of the trend plots.
+fn report_version_changes(runs: &[Run]) {
+    for pair in runs.windows(2) {
+        let [before, after] = pair else {
+            unreachable!()
+        };
+        let crates: BTreeSet<_> = before
+            .runtimes
+            .values()
+            .chain(after.runtimes.values())
+            .flat_map(BTreeMap::keys)
+            .collect();
+        for name in crates {
+            let version = |run: &Run| {
+                run.runtimes
+                    .values()
+                    .find_map(|versions| versions.get(name))
+                    .cloned()
+                    .unwrap_or_else(|| String::from("none"))
+            };
+            let (old, new) = (version(before), version(after));
+            if old != new {
+                eprintln!(
+                    "{} -> {}: {name} {old} -> {new}",
+                    before.revision, after.revision
+                );
+            }
+        }
+    }
+}
+
+/// Renders one line per runtime of `series` into the SVG file at `path`.
+fn render_trend(
+    title: &str,
+    path: &str,
+    revisions: &[String],
+    series: &BTreeMap<String, Series>,
+) -> Result<(), Box<dyn Error>> {
+    let times = series.values().flatten().map(|&(_, time)| time);
+    let min = times.clone().min_by(f64::total_cmp).unwrap_or(1.0);
+    let max = times.max_by(f64::total_cmp).unwrap_or(1.0);
+    if let Some(parent) = Path::new(path).parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let root = SVGBackend::new(path, (1280, 720)).into_drawing_area();
+    root.fill(&color::WHITE)?;
+    let mut chart = ChartBuilder::on(&root)
+        .caption(title, ("monospace", 35))
+        .margin(10)
+        .margin_right(30)
+        .x_label_area_size(60)
+        .y_label_area_size(120)
+        // A single run still needs a non-empty range to be drawn.
+        .build_cartesian_2d(
+            0..revisions.len().max(2) - 1,
+            (min * 0.8..max * 1.25).log_scale(),
+        )?;
+    let x_label_formatter = |index: &usize| revisions.get(*index).cloned().unwrap_or_default();
+    let y_label_formatter = |value: &f64| format_duration_ns(*value);
+    chart
+        .configure_mesh()
+        .x_labels(revisions.len())
+        .x_label_formatter(&x_label_formatter)
+        .y_label_formatter(&y_label_formatter)
+        .bold_line_style(BLACK.mix(0.15))
+        .x_desc("Revision")
+        .y_desc("Time (lower is better, logarithmic scale)")
+        .x_label_style(("sans-serif", 15))
+        .y_label_style(("sans-serif", 20))
+        .axis_desc_style(("sans-serif", 25))
+        .draw()?;
+    for (index, (runtime, points)) in series.iter().enumerate() {
+        let color = Palette99::pick(index).to_rgba();
+        chart
+            .draw_series(LineSeries::new(
+                points.iter().copied(),
+                color.stroke_width(2),
+            ))?
+            .label(runtime)
+            .legend(move |(x, y)| {
+                PathElement::new(vec![(x, y), (x + 20, y)], color.stroke_width(2))
+            });
+        chart.draw_series(
+            points
+                .iter()
+                .map(|&point| Circle::new(point, 3, color.filled())),
+        )?;
+    }
+    chart
+        .configure_series_labels()
+        .position(SeriesLabelPosition::UpperRight)
+        .background_style(color::WHITE.mix(0.8))
+        .border_style(BLACK.mix(0.3))
+        .label_font(("sans-serif", 15))
+        .draw()?;
+    // To avoid the IO failure being ignored silently, we manually call the present function
+    root.present()?;
+    Ok(())
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    match Args::parse().mode {
+        Mode::Record {
+            machine,
+            store,
+            revision,
+        } => record(machine, store, revision),
+        Mode::Plot {
+            machine,
+            store,
+            cases,
+        } => plot(machine, store, cases),
+    }
+}
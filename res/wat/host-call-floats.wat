@@ -0,0 +1,26 @@
+(module
+    (import "env" "host" (func $host (param f32 f64 f32 f64) (result f64)))
+    (func (export "run") (param $n i32) (result i32)
+        (local $acc f64)
+        (loop $continue
+            (local.set $acc
+                (call $host
+                    (f32.const 0.5)
+                    (local.get $acc)
+                    (f32.const 0.25)
+                    (f64.const 0.25)
+                )
+            )
+            (br_if
+                $continue
+                (local.tee $n
+                    (i32.sub
+                        (local.get $n)
+                        (i32.const 1)
+                    )
+                )
+            )
+        )
+        (return (i32.trunc_f64_s (local.get $acc)))
+    )
+)
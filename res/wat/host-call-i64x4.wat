@@ -0,0 +1,26 @@
+(module
+    (import "env" "host" (func $host (param i64 i64 i64 i64) (result i64)))
+    (func (export "run") (param $n i32) (result i32)
+        (local $acc i64)
+        (loop $continue
+            (local.set $acc
+                (call $host
+                    (local.get $acc)
+                    (i64.const 1)
+                    (i64.const 2)
+                    (i64.const 3)
+                )
+            )
+            (br_if
+                $continue
+                (local.tee $n
+                    (i32.sub
+                        (local.get $n)
+                        (i32.const 1)
+                    )
+                )
+            )
+        )
+        (return (i32.wrap_i64 (local.get $acc)))
+    )
+)
@@ -0,0 +1,25 @@
+(module
+    (import "env" "host" (func $host (param i32 i64) (result i64 i32)))
+    (func (export "run") (param $n i32) (result i32)
+        (local $a i32)
+        (local $b i64)
+        (loop $continue
+            (call $host
+                (local.get $a)
+                (local.get $b)
+            )
+            (local.set $a)
+            (local.set $b)
+            (br_if
+                $continue
+                (local.tee $n
+                    (i32.sub
+                        (local.get $n)
+                        (i32.const 1)
+                    )
+                )
+            )
+        )
+        (return (local.get $a))
+    )
+)
@@ -0,0 +1,11 @@
+(module
+    (import "env" "host" (func $host (param i32) (result i32)))
+    (func (export "run") (param $n i32) (result i32)
+        (call $host
+            (i32.add
+                (local.get $n)
+                (i32.const 1)
+            )
+        )
+    )
+)
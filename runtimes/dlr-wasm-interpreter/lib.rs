@@ -2,7 +2,9 @@
 
 use anyhow::{anyhow, bail};
 use benchmark_utils::{self as utils};
-use benchmark_utils::{ExecuteTestId, HostFunc, ModuleInstance, Runtime, RuntimeInstance, TestId};
+use benchmark_utils::{
+    ExecuteTestId, HostFunc, ModuleInstance, Runtime, RuntimeInstance, RuntimeKind, TestId,
+};
 use dlr_wasm_interpreter::{
     F32, F64, FuncType, ModuleAddr, NumType, ResultType, ValType, decode_and_validate,
 };
@@ -43,6 +45,14 @@ impl Runtime for DlrWasmInterpreter {
         "dlr-wasm-interpreter"
     }
 
+    fn label(&self) -> &'static str {
+        "DLR-wasm-interpreter"
+    }
+
+    fn kind(&self) -> RuntimeKind {
+        RuntimeKind::Interpreter
+    }
+
     fn setup(&self, id: TestId) -> Option<Box<dyn RuntimeInstance>> {
         if !Self::can_run(id) {
             return None;
@@ -55,7 +65,7 @@ impl Runtime for DlrWasmInterpreter {
 
 impl DlrWasmInterpreter {
     fn can_run(id: TestId) -> bool {
-        !matches!(id, TestId::Execute(ExecuteTestId::FibonacciTail))
+        !matches!(id.execute_case(), Some(ExecuteTestId::FibonacciTail))
     }
 }
 
@@ -198,6 +208,12 @@ impl ModuleInstance for DlrModule {
         }
     }
 
+    fn has_func(&mut self, name: &str) -> bool {
+        self.store
+            .instance_export(self.module_addr, name)
+            .is_ok_and(|export| export.as_func().is_some())
+    }
+
     fn read_memory(&mut self, name: &str, ptr: u32, buffer: &mut [u8]) -> anyhow::Result<()> {
         let memory = self
             .store
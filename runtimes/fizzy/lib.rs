@@ -2,7 +2,7 @@
 
 use anyhow::bail;
 use benchmark_utils::{self as utils, ExecuteTestId};
-use benchmark_utils::{ModuleInstance, Runtime, RuntimeInstance, TestId};
+use benchmark_utils::{ModuleInstance, Runtime, RuntimeInstance, RuntimeKind, TestId};
 use fizzy::{Config, Engine, FuncType, Instance, Linker, Module, Val, ValType};
 
 pub struct Fizzy;
@@ -12,6 +12,14 @@ impl Runtime for Fizzy {
         "fizzy"
     }
 
+    fn label(&self) -> &'static str {
+        "Fizzy"
+    }
+
+    fn kind(&self) -> RuntimeKind {
+        RuntimeKind::Interpreter
+    }
+
     fn setup(&self, id: TestId) -> Option<Box<dyn RuntimeInstance>> {
         if !self.can_run(id) {
             return None;
@@ -27,9 +35,12 @@ impl Runtime for Fizzy {
 impl Fizzy {
     fn can_run(&self, id: TestId) -> bool {
         !matches!(
-            id,
-            TestId::Execute(
-                ExecuteTestId::CounterParam | ExecuteTestId::FibonacciTail | ExecuteTestId::BulkOps
+            id.execute_case(),
+            Some(
+                ExecuteTestId::CounterParam
+                    | ExecuteTestId::FibonacciTail
+                    | ExecuteTestId::BulkOps
+                    | ExecuteTestId::HostCallMultiValue
             )
         )
     }
@@ -107,6 +118,10 @@ impl ModuleInstance for FizzyModule {
         Ok(())
     }
 
+    fn has_func(&mut self, name: &str) -> bool {
+        self.instance.get_func(name).is_some()
+    }
+
     fn read_memory(&mut self, name: &str, ptr: u32, buffer: &mut [u8]) -> anyhow::Result<()> {
         let Some(memory) = self.instance.get_memory(name) else {
             bail!("cannot find memory: {name}")
@@ -2,7 +2,9 @@
 #![cfg(any(feature = "jit", feature = "interp"))]
 
 use anyhow::{anyhow, bail};
-use benchmark_utils::{self as utils, ModuleInstance, Runtime, RuntimeInstance, TestId};
+use benchmark_utils::{
+    self as utils, ExecuteTestId, ModuleInstance, Runtime, RuntimeInstance, RuntimeKind, TestId,
+};
 pub use sf_nano_core::Tier;
 use sf_nano_core::{Caller, Config, Engine, Import, Instance, Value, WasmError};
 
@@ -29,6 +31,8 @@ struct SilverfirNanoInstance {
 struct SilverfirNanoModule {
     instance: Instance,
     params: Vec<Value>,
+    /// The names of the exported functions, since Silverfir-nano only invokes exports by name.
+    exports: Vec<Box<str>>,
 }
 
 impl Runtime for SilverfirNano {
@@ -41,6 +45,24 @@ impl Runtime for SilverfirNano {
         }
     }
 
+    fn label(&self) -> &'static str {
+        match self.tier {
+            #[cfg(feature = "jit")]
+            Tier::Jit => "Silverfir-nano (JIT)",
+            #[cfg(feature = "interp")]
+            Tier::Interp => "Silverfir-nano (interpreter)",
+        }
+    }
+
+    fn kind(&self) -> RuntimeKind {
+        match self.tier {
+            #[cfg(feature = "jit")]
+            Tier::Jit => RuntimeKind::Jit,
+            #[cfg(feature = "interp")]
+            Tier::Interp => RuntimeKind::Interpreter,
+        }
+    }
+
     fn setup(&self, id: TestId) -> Option<Box<dyn RuntimeInstance>> {
         if !self.can_run(id) {
             return None;
@@ -58,8 +80,19 @@ impl Runtime for SilverfirNano {
 }
 
 impl SilverfirNano {
-    fn can_run(&self, _id: TestId) -> bool {
-        true
+    fn can_run(&self, id: TestId) -> bool {
+        // Note: the `host-call-*` cases that measure calls into imported host functions are
+        // skipped since those are dispatched across the runtime-neutral value boundary of
+        // `dispatch_host_func`, which would dominate the measurement.
+        !matches!(
+            id.execute_case(),
+            Some(
+                ExecuteTestId::HostCallNullary
+                    | ExecuteTestId::HostCallI64x4
+                    | ExecuteTestId::HostCallFloats
+                    | ExecuteTestId::HostCallMultiValue
+            )
+        )
     }
 }
 
@@ -72,9 +105,9 @@ impl RuntimeInstance for SilverfirNanoInstance {
         func: fn(params: &[utils::Val], results: &mut [utils::Val]),
     ) {
         // Recorded here and replayed as a real Silverfir-nano import in `instantiate`, where each
-        // call is dispatched to `func`. In practice the benchmarks never call these (execute cases
-        // import nothing; startup cases only link imports to satisfy instantiation, which is all
-        // that is timed), but the wiring is faithful rather than an inert stub.
+        // call is dispatched to `func`. The `host-call-*` execute cases calling imports are skipped
+        // in `setup`, so only startup cases link imports to satisfy instantiation, which is all
+        // that is timed, but the wiring is faithful rather than an inert stub.
         self.linker.define(module, name, ty, func);
     }
 
@@ -102,6 +135,7 @@ impl RuntimeInstance for SilverfirNanoInstance {
         Box::new(SilverfirNanoModule {
             instance,
             params: Vec::new(),
+            exports: utils::func_exports(wasm),
         })
     }
 }
@@ -133,6 +167,10 @@ impl ModuleInstance for SilverfirNanoModule {
         Ok(())
     }
 
+    fn has_func(&mut self, name: &str) -> bool {
+        self.exports.iter().any(|export| &**export == name)
+    }
+
     fn read_memory(&mut self, _name: &str, ptr: u32, buffer: &mut [u8]) -> anyhow::Result<()> {
         let memory = self
             .instance
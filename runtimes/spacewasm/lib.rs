@@ -8,7 +8,9 @@
 //! [SpaceWasm]: https://github.com/nasa/spacewasm
 
 use benchmark_utils::{self as utils};
-use benchmark_utils::{ExecuteTestId, ModuleInstance, Runtime, RuntimeInstance, TestId};
+use benchmark_utils::{
+    ExecuteTestId, ModuleInstance, Runtime, RuntimeInstance, RuntimeKind, TestId,
+};
 use spacewasm::{
     AllocError, Allocator, CodeBuilder, CompilerOptions, Engine, ExportDesc,
     HOST_FUNCTION_NAME_CAP, HOST_MODULE_NAME_CAP, HostFunction, HostModule, HostName, HostValList,
@@ -112,6 +114,14 @@ impl Runtime for SpaceWasm {
         "spacewasm"
     }
 
+    fn label(&self) -> &'static str {
+        "SpaceWasm"
+    }
+
+    fn kind(&self) -> RuntimeKind {
+        RuntimeKind::Interpreter
+    }
+
     fn setup(&self, id: TestId) -> Option<Box<dyn RuntimeInstance>> {
         if !self.can_run(id) {
             return None;
@@ -129,10 +139,14 @@ impl SpaceWasm {
         //   - `fibonacci-tail` uses the tail-call proposal (`return_call`).
         //   - `counter-param` uses multi-value block signatures (`loop (param i32) (result i32)`).
         //   - `bulk-ops` uses the bulk-memory opcodes (`0xFC` prefix).
+        //   - `host-call-multi-value` imports a host function with multiple results.
         !matches!(
-            id,
-            TestId::Execute(
-                ExecuteTestId::FibonacciTail | ExecuteTestId::CounterParam | ExecuteTestId::BulkOps
+            id.execute_case(),
+            Some(
+                ExecuteTestId::FibonacciTail
+                    | ExecuteTestId::CounterParam
+                    | ExecuteTestId::BulkOps
+                    | ExecuteTestId::HostCallMultiValue
             )
         )
     }
@@ -265,6 +279,14 @@ impl ModuleInstance for SpaceWasmModule {
         Ok(())
     }
 
+    fn has_func(&mut self, name: &str) -> bool {
+        let module = &self.engine.store.modules()[self.module_index];
+        module
+            .exports
+            .iter()
+            .any(|export| &*export.name == name && matches!(export.desc, ExportDesc::Func(_)))
+    }
+
     fn read_memory(&mut self, name: &str, ptr: u32, buffer: &mut [u8]) -> anyhow::Result<()> {
         let bytes = self
             .memory(name)?
@@ -2,7 +2,9 @@
 
 use anyhow::bail;
 use benchmark_utils as utils;
-use benchmark_utils::{ExecuteTestId, ModuleInstance, Runtime, RuntimeInstance, TestId};
+use benchmark_utils::{
+    ExecuteTestId, ModuleInstance, Runtime, RuntimeInstance, RuntimeKind, TestId,
+};
 use makepad_stitch::{Engine, ExternVal, Func, Instance, Linker, Module, Store, Val, ValType};
 
 pub struct Stitch;
@@ -23,6 +25,14 @@ impl Runtime for Stitch {
         "stitch"
     }
 
+    fn label(&self) -> &'static str {
+        "Stitch (lazy)"
+    }
+
+    fn kind(&self) -> RuntimeKind {
+        RuntimeKind::Interpreter
+    }
+
     fn setup(&self, id: TestId) -> Option<Box<dyn RuntimeInstance>> {
         if !self.can_run(id) {
             return None;
@@ -35,7 +45,11 @@ impl Runtime for Stitch {
 
 impl Stitch {
     fn can_run(&self, id: TestId) -> bool {
-        !matches!(id, TestId::Execute(ExecuteTestId::FibonacciTail))
+        // Note: `wrap_host_func` only covers host functions with at most one result.
+        !matches!(
+            id.execute_case(),
+            Some(ExecuteTestId::FibonacciTail | ExecuteTestId::HostCallMultiValue)
+        )
     }
 }
 
@@ -91,6 +105,10 @@ impl ModuleInstance for StitchModule {
         Ok(())
     }
 
+    fn has_func(&mut self, name: &str) -> bool {
+        self.instance.exported_func(name).is_some()
+    }
+
     fn read_memory(&mut self, name: &str, ptr: u32, buffer: &mut [u8]) -> anyhow::Result<()> {
         let Some(memory) = self.instance.exported_mem(name) else {
             bail!("memory not found: {name}")
@@ -160,13 +178,27 @@ fn ret_i32(func: utils::HostFunc, params: &[utils::Val]) -> i32 {
     out[0].unwrap_i32()
 }
 
+/// Invokes a recorded host `func` that produces a single `i64` result.
+fn ret_i64(func: utils::HostFunc, params: &[utils::Val]) -> i64 {
+    let mut out = [utils::Val::I64(0)];
+    func(params, &mut out);
+    out[0].unwrap_i64()
+}
+
+/// Invokes a recorded host `func` that produces a single `f64` result.
+fn ret_f64(func: utils::HostFunc, params: &[utils::Val]) -> f64 {
+    let mut out = [utils::Val::F64(0.0)];
+    func(params, &mut out);
+    out[0].unwrap_f64()
+}
+
 /// Builds a stitch [`Func`] from a runtime-neutral signature and host `func` pointer.
 ///
 /// Stitch only offers the typed `Func::wrap` constructor, so every signature imported by the
 /// benchmark inputs is enumerated here. Any unsupported signature hits the catch-all `unimplemented`.
 fn wrap_host_func(store: &mut Store, ty: &utils::FuncType, func: utils::HostFunc) -> Func {
-    use utils::Val::{I32 as V32, I64 as V64};
-    use utils::ValType::{I32, I64};
+    use utils::Val::{F32 as VF32, F64 as VF64, I32 as V32, I64 as V64};
+    use utils::ValType::{F32, F64, I32, I64};
     match (ty.params(), ty.results()) {
         ([], []) => Func::wrap(store, move || call(func, &[])),
         ([], [I32]) => Func::wrap(store, move || -> i32 { ret_i32(func, &[]) }),
@@ -235,6 +267,16 @@ fn wrap_host_func(store: &mut Store, ty: &utils::FuncType, func: utils::HostFunc
                 )
             },
         ),
+        ([I64, I64, I64, I64], [I64]) => {
+            Func::wrap(store, move |a: i64, b: i64, c: i64, d: i64| -> i64 {
+                ret_i64(func, &[V64(a), V64(b), V64(c), V64(d)])
+            })
+        }
+        ([F32, F64, F32, F64], [F64]) => {
+            Func::wrap(store, move |a: f32, b: f64, c: f32, d: f64| -> f64 {
+                ret_f64(func, &[VF32(a), VF64(b), VF32(c), VF64(d)])
+            })
+        }
         _ => unimplemented!("the stitch adapter does not support host function signature {ty:?}"),
     }
 }
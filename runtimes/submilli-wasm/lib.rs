@@ -2,7 +2,7 @@
 
 use anyhow::bail;
 use benchmark_utils as utils;
-use benchmark_utils::{ModuleInstance, Runtime, RuntimeInstance, TestId};
+use benchmark_utils::{ModuleInstance, Runtime, RuntimeInstance, RuntimeKind, TestId};
 use submilli_wasm::{Val, ValType};
 
 pub struct SubmilliWasm;
@@ -25,6 +25,14 @@ impl Runtime for SubmilliWasm {
         "submilli-wasm"
     }
 
+    fn label(&self) -> &'static str {
+        "Submilli-wasm"
+    }
+
+    fn kind(&self) -> RuntimeKind {
+        RuntimeKind::Interpreter
+    }
+
     fn setup(&self, id: TestId) -> Option<Box<dyn RuntimeInstance>> {
         if !self.can_run(id) {
             return None;
@@ -117,6 +125,10 @@ impl ModuleInstance for SubmilliWasmModule {
         Ok(())
     }
 
+    fn has_func(&mut self, name: &str) -> bool {
+        self.instance.get_func(&mut self.store, name).is_some()
+    }
+
     fn read_memory(&mut self, name: &str, ptr: u32, buffer: &mut [u8]) -> anyhow::Result<()> {
         let Some(memory) = self.instance.get_memory(&mut self.store, name) else {
             bail!("memory not found: {name}")
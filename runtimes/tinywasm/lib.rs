@@ -1,7 +1,7 @@
 #![crate_type = "dylib"]
 
 use benchmark_utils as utils;
-use benchmark_utils::{ModuleInstance, Runtime, RuntimeInstance, TestId};
+use benchmark_utils::{ModuleInstance, Runtime, RuntimeInstance, RuntimeKind, TestId};
 use tinywasm::types::{FuncType as TinyFuncType, WasmType, WasmValue as Val};
 
 pub struct Tinywasm;
@@ -26,6 +26,14 @@ impl Runtime for Tinywasm {
         "tinywasm"
     }
 
+    fn label(&self) -> &'static str {
+        "Tinywasm"
+    }
+
+    fn kind(&self) -> RuntimeKind {
+        RuntimeKind::Interpreter
+    }
+
     fn setup(&self, id: TestId) -> Option<Box<dyn RuntimeInstance>> {
         if !self.can_run(id) {
             return None;
@@ -107,6 +115,10 @@ impl ModuleInstance for TinywasmModule {
         Ok(())
     }
 
+    fn has_func(&mut self, name: &str) -> bool {
+        self.instance.func_untyped(&self.store, name).is_ok()
+    }
+
     fn read_memory(&mut self, name: &str, ptr: u32, buffer: &mut [u8]) -> anyhow::Result<()> {
         let memory = self.instance.memory(name)?;
         memory.read(&self.store, ptr as usize, buffer)?;
@@ -2,7 +2,7 @@
 
 use anyhow::bail;
 use benchmark_utils::{self as utils};
-use benchmark_utils::{ModuleInstance, Runtime, RuntimeInstance, TestId};
+use benchmark_utils::{ModuleInstance, Runtime, RuntimeInstance, RuntimeKind, TestId};
 use toywasmx::{FuncType, Store, Val, ValType};
 
 pub struct Toywasm;
@@ -29,6 +29,14 @@ impl Runtime for Toywasm {
         "toywasm"
     }
 
+    fn label(&self) -> &'static str {
+        "Toywasm"
+    }
+
+    fn kind(&self) -> RuntimeKind {
+        RuntimeKind::Interpreter
+    }
+
     fn setup(&self, _id: TestId) -> Option<Box<dyn RuntimeInstance>> {
         Some(Box::new(ToywasmInstance {
             linker: utils::Linker::new(),
@@ -100,6 +108,10 @@ impl ModuleInstance for ToywasmModule {
         Ok(())
     }
 
+    fn has_func(&mut self, name: &str) -> bool {
+        self.instance.get_func(name).is_ok()
+    }
+
     fn read_memory(&mut self, name: &str, ptr: u32, buffer: &mut [u8]) -> anyhow::Result<()> {
         let memory = self.instance.get_memory(name)?;
         let data = memory.data();
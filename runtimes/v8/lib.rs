@@ -1,7 +1,7 @@
 #![crate_type = "dylib"]
 
 use benchmark_utils::{self as utils, HostFunc};
-use benchmark_utils::{ModuleInstance, Runtime, RuntimeInstance, TestId};
+use benchmark_utils::{ModuleInstance, Runtime, RuntimeInstance, RuntimeKind, TestId};
 use std::cell::RefCell;
 use std::ffi::c_void;
 use std::sync::Once;
@@ -48,6 +48,14 @@ impl Runtime for V8 {
         "v8"
     }
 
+    fn label(&self) -> &'static str {
+        "V8"
+    }
+
+    fn kind(&self) -> RuntimeKind {
+        RuntimeKind::Jit
+    }
+
     fn setup(&self, id: TestId) -> Option<Box<dyn RuntimeInstance>> {
         if !self.can_run(id) {
             return None;
@@ -189,6 +197,18 @@ impl ModuleInstance for V8Module {
         Ok(())
     }
 
+    fn has_func(&mut self, name: &str) -> bool {
+        let mut isolate = self.isolate.borrow_mut();
+        v8::scope_with_context!(let scope, &mut *isolate, &self.context);
+        let exports = v8::Local::new(scope, &self.exports);
+        let Some(name_key) = v8::String::new(scope, name) else {
+            return false;
+        };
+        exports
+            .get(scope, name_key.into())
+            .is_some_and(|export| export.is_function())
+    }
+
     fn read_memory(&mut self, name: &str, ptr: u32, buffer: &mut [u8]) -> anyhow::Result<()> {
         let mut isolate = self.isolate.borrow_mut();
         v8::scope_with_context!(let scope, &mut *isolate, &self.context);
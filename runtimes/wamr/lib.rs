@@ -2,7 +2,7 @@
 
 use anyhow::bail;
 use benchmark_utils::{self as utils};
-use benchmark_utils::{ModuleInstance, Runtime, RuntimeInstance, TestId};
+use benchmark_utils::{ModuleInstance, Runtime, RuntimeInstance, RuntimeKind, TestId};
 use wamr::{Engine, Func, FuncType, Instance, Linker, Module, Val, ValType};
 
 pub struct Wamr;
@@ -23,6 +23,14 @@ impl Runtime for Wamr {
         "wamr"
     }
 
+    fn label(&self) -> &'static str {
+        "WAMR fast-interpreter"
+    }
+
+    fn kind(&self) -> RuntimeKind {
+        RuntimeKind::Interpreter
+    }
+
     fn setup(&self, _id: TestId) -> Option<Box<dyn RuntimeInstance>> {
         let engine = Engine::new().unwrap();
         let linker = Linker::new(&engine);
@@ -86,6 +94,10 @@ impl ModuleInstance for WamrModule {
         Ok(())
     }
 
+    fn has_func(&mut self, name: &str) -> bool {
+        self.instance.get_func(name).is_ok()
+    }
+
     fn read_memory(&mut self, name: &str, ptr: u32, buffer: &mut [u8]) -> anyhow::Result<()> {
         let memory = self.instance.get_memory(name)?;
         let data = memory.data();
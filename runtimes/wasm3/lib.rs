@@ -2,7 +2,7 @@
 
 use anyhow::bail;
 use benchmark_utils::{self as utils, ExecuteTestId};
-use benchmark_utils::{ModuleInstance, Runtime, RuntimeInstance, TestId};
+use benchmark_utils::{ModuleInstance, Runtime, RuntimeInstance, RuntimeKind, TestId};
 pub use wasm3::CompilationMode;
 use wasm3::{Func, Val};
 
@@ -31,6 +31,17 @@ impl Runtime for Wasm3 {
         }
     }
 
+    fn label(&self) -> &'static str {
+        match self.compilation_mode {
+            CompilationMode::Eager => "Wasm3 (eager)",
+            CompilationMode::Lazy => "Wasm3 (lazy)",
+        }
+    }
+
+    fn kind(&self) -> RuntimeKind {
+        RuntimeKind::Interpreter
+    }
+
     fn setup(&self, id: TestId) -> Option<Box<dyn RuntimeInstance>> {
         if !self.can_run(id) {
             return None;
@@ -47,6 +58,7 @@ impl Wasm3 {
                 !matches!(id, TestId::Execute(ExecuteTestId::FibonacciTail))
                     && matches!(self.compilation_mode, CompilationMode::Eager)
             }
+            TestId::FirstCall(id) => !matches!(id, ExecuteTestId::FibonacciTail),
             _ => true,
         }
     }
@@ -130,6 +142,10 @@ impl ModuleInstance for Wasm3Module {
         Ok(())
     }
 
+    fn has_func(&mut self, name: &str) -> bool {
+        self.instance.get_func(&self.store, name).is_some()
+    }
+
     fn read_memory(&mut self, name: &str, ptr: u32, buffer: &mut [u8]) -> anyhow::Result<()> {
         let Some(memory) = self.instance.get_memory(&self.store) else {
             bail!("memory not found: {name}")
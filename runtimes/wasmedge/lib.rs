@@ -2,7 +2,7 @@
 
 use anyhow::bail;
 use benchmark_utils::{self as utils};
-use benchmark_utils::{ModuleInstance, Runtime, RuntimeInstance, TestId};
+use benchmark_utils::{ModuleInstance, Runtime, RuntimeInstance, RuntimeKind, TestId};
 use std::collections::BTreeMap;
 use wasmedge::{
     AsInstance, CallingFrame, Config, Executor, FuncType, Function, ImportModule, Instance, Loader,
@@ -40,6 +40,14 @@ impl Runtime for WasmEdge {
         "wasmedge"
     }
 
+    fn label(&self) -> &'static str {
+        "WasmEdge (interpreter)"
+    }
+
+    fn kind(&self) -> RuntimeKind {
+        RuntimeKind::Interpreter
+    }
+
     fn setup(&self, id: TestId) -> Option<Box<dyn RuntimeInstance>> {
         if !self.can_run(id) {
             return None;
@@ -157,6 +165,10 @@ impl ModuleInstance for WasmEdgeModule {
         Ok(())
     }
 
+    fn has_func(&mut self, name: &str) -> bool {
+        self.instance.get_func_mut(name).is_ok()
+    }
+
     fn read_memory(&mut self, name: &str, ptr: u32, buffer: &mut [u8]) -> anyhow::Result<()> {
         let memory = self.instance.get_memory_ref(name)?;
         let Some(bytes) = memory.slice::<u8>(ptr as usize, buffer.len()) else {
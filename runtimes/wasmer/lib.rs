@@ -1,7 +1,11 @@
 #![crate_type = "dylib"]
 
 use benchmark_utils::{self as utils};
-use benchmark_utils::{ExecuteTestId, ModuleInstance, Runtime, RuntimeInstance, TestId};
+use benchmark_utils::{
+    ExecuteTestId, Metering, ModuleInstance, Runtime, RuntimeInstance, RuntimeKind, TestId,
+};
+use core::any::Any;
+use std::sync::Arc;
 use wasmer::Type as ValType;
 use wasmer::Value as Val;
 
@@ -31,6 +35,8 @@ struct WasmerModule {
     store: wasmer::Store,
     instance: wasmer::Instance,
     params: Vec<Val>,
+    /// The functions resolved by [`ModuleInstance::get_func`].
+    funcs: utils::ResolvedFuncs<wasmer::Function>,
 }
 
 impl Runtime for Wasmer {
@@ -41,20 +47,47 @@ impl Runtime for Wasmer {
         }
     }
 
+    fn label(&self) -> &'static str {
+        match self.compiler {
+            WasmerCompiler::Cranelift => "Wasmer (Cranelift)",
+            WasmerCompiler::Singlepass => "Wasmer (Singlepass)",
+        }
+    }
+
+    fn kind(&self) -> RuntimeKind {
+        RuntimeKind::Jit
+    }
+
     fn setup(&self, id: TestId) -> Option<Box<dyn RuntimeInstance>> {
+        self.setup_with(id, false)
+    }
+
+    fn supports_metering(&self, metering: Metering) -> bool {
+        // Wasmer meters via its metering middleware but has no epoch interruption.
+        matches!(metering, Metering::Fuel)
+    }
+
+    fn setup_metered(&self, id: TestId, metering: Metering) -> Option<Box<dyn RuntimeInstance>> {
+        if !self.supports_metering(metering) {
+            return None;
+        }
+        self.setup_with(id, true)
+    }
+}
+
+impl Wasmer {
+    fn setup_with(&self, id: TestId, metered: bool) -> Option<Box<dyn RuntimeInstance>> {
         if !self.can_run(id) {
             return None;
         }
         Some(Box::new(WasmerInstance {
-            engine: make_engine(self.compiler),
+            engine: make_engine(self.compiler, metered),
             linker: utils::Linker::new(),
         }))
     }
-}
 
-impl Wasmer {
     fn can_run(&self, id: TestId) -> bool {
-        !matches!(id, TestId::Execute(ExecuteTestId::FibonacciTail))
+        !matches!(id.execute_case(), Some(ExecuteTestId::FibonacciTail))
     }
 }
 
@@ -69,6 +102,12 @@ impl RuntimeInstance for WasmerInstance {
         self.linker.define(module, name, ty, func);
     }
 
+    fn compiled_size(&self, wasm: &[u8]) -> Option<usize> {
+        let store = wasmer::Store::new(self.engine.clone());
+        let module = wasmer::Module::new(&store, wasm).unwrap();
+        Some(module.serialize().unwrap().len())
+    }
+
     fn instantiate(&self, wasm: &[u8]) -> Box<dyn ModuleInstance> {
         // Note: Wasmer binds host functions to a `Store`, so the recorded functions are (re)built against
         //       a fresh store (from the reusable engine) and imports on every instantiation.
@@ -97,17 +136,29 @@ impl RuntimeInstance for WasmerInstance {
             store,
             instance,
             params: Vec::new(),
+            funcs: utils::ResolvedFuncs::new(),
         })
     }
 }
 
-fn make_engine(compiler: WasmerCompiler) -> wasmer::Engine {
+/// Adds the metering middleware to `compiler`, charging one point per executed operator.
+///
+/// Every instance starts out with all points, so the middleware never interrupts execution.
+fn push_metering(compiler: &mut impl wasmer::sys::CompilerConfig) {
+    let metering = wasmer_middlewares::Metering::new(u64::MAX, |_: &_| 1);
+    compiler.push_middleware(Arc::new(metering));
+}
+
+fn make_engine(compiler: WasmerCompiler, metered: bool) -> wasmer::Engine {
     match compiler {
         #[cfg(feature = "cranelift")]
         WasmerCompiler::Cranelift => {
             let mut compiler = wasmer_compiler_cranelift::Cranelift::new();
             // Compile on a single thread so compile timings don't depend on the host's core count.
             compiler.num_threads(core::num::NonZero::new(1).unwrap());
+            if metered {
+                push_metering(&mut compiler);
+            }
             let builder = wasmer::sys::EngineBuilder::new(compiler);
             let mut features = wasmer::sys::Features::new();
             features.tail_call(true);
@@ -118,6 +169,9 @@ fn make_engine(compiler: WasmerCompiler) -> wasmer::Engine {
             let mut compiler = wasmer_compiler_singlepass::Singlepass::new();
             // Compile on a single thread so compile timings don't depend on the host's core count.
             compiler.num_threads(core::num::NonZero::new(1).unwrap());
+            if metered {
+                push_metering(&mut compiler);
+            }
             let builder = wasmer::sys::EngineBuilder::new(compiler);
             builder.engine().into()
         }
@@ -134,12 +188,40 @@ impl ModuleInstance for WasmerModule {
         results: &mut [utils::Val],
     ) -> anyhow::Result<()> {
         let func = self.instance.exports.get_function(name).cloned()?;
-        assert_eq!(params.len(), func.ty(&self.store).params().len());
-        assert_eq!(results.len(), func.ty(&self.store).results().len());
-        self.prepare_params(params);
-        let call_results = func.call(&mut self.store, &self.params[..])?;
-        self.write_back_results(results, &call_results);
-        Ok(())
+        self.call_untyped(&func, params, results)
+    }
+
+    fn has_func(&mut self, name: &str) -> bool {
+        self.instance.exports.get_function(name).is_ok()
+    }
+
+    fn get_func(&mut self, name: &str) -> anyhow::Result<utils::ExportedFunc> {
+        self.funcs.resolve(name, || {
+            Ok(self.instance.exports.get_function(name).cloned()?)
+        })
+    }
+
+    fn call_func(
+        &mut self,
+        func: &utils::ExportedFunc,
+        params: &[utils::Val],
+        results: &mut [utils::Val],
+    ) -> anyhow::Result<()> {
+        let func = self.funcs.get(func)?.clone();
+        self.call_untyped(&func, params, results)
+    }
+
+    fn call_func_typed(
+        &mut self,
+        func: &utils::ExportedFunc,
+        call: &mut dyn Any,
+    ) -> anyhow::Result<bool> {
+        utils::dispatch_typed_call!(call, |params: Params| -> Results {
+            let typed = self
+                .funcs
+                .typed(func, |func| Ok(func.typed::<Params, Results>(&self.store)?))?;
+            typed.call_with(&mut self.store, params)?
+        })
     }
 
     fn read_memory(&mut self, name: &str, ptr: u32, buffer: &mut [u8]) -> anyhow::Result<()> {
@@ -156,6 +238,18 @@ impl ModuleInstance for WasmerModule {
 }
 
 impl WasmerModule {
+    fn call_untyped(
+        &mut self,
+        func: &wasmer::Function,
+        params: &[utils::Val],
+        results: &mut [utils::Val],
+    ) -> anyhow::Result<()> {
+        self.prepare_params(params);
+        let call_results = func.call(&mut self.store, &self.params[..])?;
+        self.write_back_results(results, &call_results);
+        Ok(())
+    }
+
     fn prepare_params(&mut self, params: &[utils::Val]) {
         self.params.clear();
         self.params
@@ -170,6 +264,52 @@ impl WasmerModule {
     }
 }
 
+/// Calls a [`wasmer::TypedFunction`] with its parameters as a single value.
+///
+/// Wasmer takes every parameter as a separate argument instead, which does not fit
+/// [`utils::dispatch_typed_call!`].
+trait CallWith<Params, Results> {
+    fn call_with(
+        &self,
+        store: &mut wasmer::Store,
+        params: Params,
+    ) -> Result<Results, wasmer::RuntimeError>;
+}
+
+impl<Results> CallWith<(), Results> for wasmer::TypedFunction<(), Results>
+where
+    Results: wasmer::WasmTypeList,
+{
+    fn call_with(
+        &self,
+        store: &mut wasmer::Store,
+        (): (),
+    ) -> Result<Results, wasmer::RuntimeError> {
+        self.call(store)
+    }
+}
+
+/// Implements [`CallWith`] for typed functions with a single parameter of the given types.
+macro_rules! impl_call_with {
+    ( $( $ty:ty ),* ) => {
+        $(
+            impl<Results> CallWith<$ty, Results> for wasmer::TypedFunction<$ty, Results>
+            where
+                Results: wasmer::WasmTypeList,
+            {
+                fn call_with(
+                    &self,
+                    store: &mut wasmer::Store,
+                    params: $ty,
+                ) -> Result<Results, wasmer::RuntimeError> {
+                    self.call(store, params)
+                }
+            }
+        )*
+    };
+}
+impl_call_with!(i32, i64, f32, f64);
+
 fn to_wasmer_type(ty: utils::ValType) -> ValType {
     match ty {
         utils::ValType::I32 => ValType::I32,
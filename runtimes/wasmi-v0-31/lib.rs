@@ -2,7 +2,8 @@
 
 use anyhow::bail;
 use benchmark_utils::{self as utils};
-use benchmark_utils::{ModuleInstance, Runtime, RuntimeInstance, TestId};
+use benchmark_utils::{Metering, ModuleInstance, Runtime, RuntimeInstance, RuntimeKind, TestId};
+use core::any::Any;
 use wasmi::Func;
 use wasmi::Value as Val;
 
@@ -11,6 +12,8 @@ pub struct WasmiV031;
 /// A concrete Wasmi runtime with its linker, produced by [`WasmiV031::setup`].
 struct WasmiInstance {
     linker: wasmi::Linker<()>,
+    /// Whether the engine consumes fuel, refilled for every instance.
+    fuel: bool,
 }
 
 /// An instantiated Wasmi module, produced by [`WasmiInstance::instantiate`].
@@ -19,6 +22,8 @@ struct WasmiModule {
     instance: wasmi::Instance,
     params: Vec<Val>,
     results: Vec<Val>,
+    /// The functions resolved by [`ModuleInstance::get_func`].
+    funcs: utils::ResolvedFuncs<Func>,
 }
 
 impl Runtime for WasmiV031 {
@@ -26,9 +31,37 @@ impl Runtime for WasmiV031 {
         "wasmi-v0.31"
     }
 
+    fn label(&self) -> &'static str {
+        "Wasmi v0.31"
+    }
+
+    fn kind(&self) -> RuntimeKind {
+        RuntimeKind::Interpreter
+    }
+
+    fn is_outdated(&self) -> bool {
+        // Superseded by Wasmi v2.
+        true
+    }
+
     fn setup(&self, _id: TestId) -> Option<Box<dyn RuntimeInstance>> {
-        let linker = wasmi::Linker::new(&make_engine());
-        Some(Box::new(WasmiInstance { linker }))
+        let linker = wasmi::Linker::new(&make_engine(false));
+        Some(Box::new(WasmiInstance {
+            linker,
+            fuel: false,
+        }))
+    }
+
+    fn supports_metering(&self, metering: Metering) -> bool {
+        matches!(metering, Metering::Fuel)
+    }
+
+    fn setup_metered(&self, _id: TestId, metering: Metering) -> Option<Box<dyn RuntimeInstance>> {
+        if !self.supports_metering(metering) {
+            return None;
+        }
+        let linker = wasmi::Linker::new(&make_engine(true));
+        Some(Box::new(WasmiInstance { linker, fuel: true }))
     }
 }
 
@@ -71,6 +104,9 @@ impl RuntimeInstance for WasmiInstance {
     fn instantiate(&self, wasm: &[u8]) -> Box<dyn ModuleInstance> {
         let engine = self.linker.engine().clone();
         let mut store = <wasmi::Store<()>>::new(&engine, ());
+        if self.fuel {
+            store.add_fuel(u64::MAX).unwrap();
+        }
         let module = wasmi::Module::new(&engine, wasm).unwrap();
         let instance = self
             .linker
@@ -83,13 +119,15 @@ impl RuntimeInstance for WasmiInstance {
             instance,
             params: Vec::new(),
             results: Vec::new(),
+            funcs: utils::ResolvedFuncs::new(),
         })
     }
 }
 
-fn make_engine() -> wasmi::Engine {
+fn make_engine(fuel: bool) -> wasmi::Engine {
     let mut config = wasmi::Config::default();
     config.wasm_tail_call(true);
+    config.consume_fuel(fuel);
     wasmi::Engine::new(&config)
 }
 
@@ -103,13 +141,44 @@ impl ModuleInstance for WasmiModule {
         let Some(func) = self.instance.get_func(&self.store, name) else {
             anyhow::bail!("failed to find function")
         };
-        assert_eq!(params.len(), func.ty(&self.store).params().len());
-        assert_eq!(results.len(), func.ty(&self.store).results().len());
-        self.prepare_params(params);
-        self.prepare_results(&func);
-        func.call(&mut self.store, &self.params[..], &mut self.results[..])?;
-        self.write_back_results(results);
-        Ok(())
+        self.call_untyped(func, params, results)
+    }
+
+    fn has_func(&mut self, name: &str) -> bool {
+        self.instance.get_func(&self.store, name).is_some()
+    }
+
+    fn get_func(&mut self, name: &str) -> anyhow::Result<utils::ExportedFunc> {
+        self.funcs.resolve(name, || {
+            let Some(func) = self.instance.get_func(&self.store, name) else {
+                anyhow::bail!("failed to find function")
+            };
+            Ok(func)
+        })
+    }
+
+    fn call_func(
+        &mut self,
+        func: &utils::ExportedFunc,
+        params: &[utils::Val],
+        results: &mut [utils::Val],
+    ) -> anyhow::Result<()> {
+        let func = *self.funcs.get(func)?;
+        self.call_untyped(func, params, results)
+    }
+
+    fn call_func_typed(
+        &mut self,
+        func: &utils::ExportedFunc,
+        call: &mut dyn Any,
+    ) -> anyhow::Result<bool> {
+        // Note: Wasmi v0.31 only types `f32` and `f64` via its own `F32` and `F64`.
+        utils::dispatch_typed_call!(call, [i32 i64], |params: Params| -> Results {
+            let typed = self
+                .funcs
+                .typed(func, |func| Ok(func.typed::<Params, Results>(&self.store)?))?;
+            typed.call(&mut self.store, params)?
+        })
     }
 
     fn read_memory(&mut self, name: &str, ptr: u32, buffer: &mut [u8]) -> anyhow::Result<()> {
@@ -140,6 +209,19 @@ impl ModuleInstance for WasmiModule {
 }
 
 impl WasmiModule {
+    fn call_untyped(
+        &mut self,
+        func: Func,
+        params: &[utils::Val],
+        results: &mut [utils::Val],
+    ) -> anyhow::Result<()> {
+        self.prepare_params(params);
+        self.prepare_results(&func);
+        func.call(&mut self.store, &self.params[..], &mut self.results[..])?;
+        self.write_back_results(results);
+        Ok(())
+    }
+
     fn prepare_params(&mut self, params: &[utils::Val]) {
         self.params.clear();
         self.params
@@ -2,7 +2,8 @@
 
 use anyhow::bail;
 use benchmark_utils::{self as utils};
-use benchmark_utils::{ModuleInstance, Runtime, RuntimeInstance, TestId};
+use benchmark_utils::{Metering, ModuleInstance, Runtime, RuntimeInstance, RuntimeKind, TestId};
+use core::any::Any;
 pub use wasmi::CompilationMode;
 use wasmi::{Func, Val};
 
@@ -21,6 +22,8 @@ pub enum Validation {
 struct WasmiInstance {
     linker: wasmi::Linker<()>,
     validation: Validation,
+    /// Whether the engine consumes fuel, refilled for every instance.
+    fuel: bool,
 }
 
 /// An instantiated Wasmi module, produced by [`WasmiInstance::instantiate`].
@@ -29,6 +32,8 @@ struct WasmiModule {
     instance: wasmi::Instance,
     params: Vec<Val>,
     results: Vec<Val>,
+    /// The functions resolved by [`ModuleInstance::get_func`].
+    funcs: utils::ResolvedFuncs<Func>,
 }
 
 impl Runtime for Wasmi {
@@ -47,19 +52,59 @@ impl Runtime for Wasmi {
         }
     }
 
+    fn label(&self) -> &'static str {
+        match (self.compilation_mode, self.validation) {
+            (CompilationMode::Eager, Validation::Checked) => "Wasmi v1 (eager)",
+            (CompilationMode::Eager, Validation::Unchecked) => "Wasmi v1 (eager, unchecked)",
+            (CompilationMode::LazyTranslation, Validation::Checked) => {
+                "Wasmi v1 (lazy-translation)"
+            }
+            (CompilationMode::LazyTranslation, Validation::Unchecked) => {
+                "Wasmi v1 (lazy-translation, unchecked)"
+            }
+            (CompilationMode::Lazy, Validation::Checked) => "Wasmi v1 (lazy)",
+            (CompilationMode::Lazy, Validation::Unchecked) => "Wasmi v1 (lazy, unchecked)",
+        }
+    }
+
+    fn kind(&self) -> RuntimeKind {
+        RuntimeKind::Interpreter
+    }
+
+    fn is_outdated(&self) -> bool {
+        // Superseded by Wasmi v2.
+        true
+    }
+
     fn setup(&self, id: TestId) -> Option<Box<dyn RuntimeInstance>> {
+        self.setup_with(id, false)
+    }
+
+    fn supports_metering(&self, metering: Metering) -> bool {
+        matches!(metering, Metering::Fuel)
+    }
+
+    fn setup_metered(&self, id: TestId, metering: Metering) -> Option<Box<dyn RuntimeInstance>> {
+        if !self.supports_metering(metering) {
+            return None;
+        }
+        self.setup_with(id, true)
+    }
+}
+
+impl Wasmi {
+    fn setup_with(&self, id: TestId, fuel: bool) -> Option<Box<dyn RuntimeInstance>> {
         if !self.can_run(id) {
             return None;
         }
-        let linker = wasmi::Linker::new(&make_engine(self.compilation_mode));
+        let linker = wasmi::Linker::new(&make_engine(self.compilation_mode, fuel));
         Some(Box::new(WasmiInstance {
             linker,
             validation: self.validation,
+            fuel,
         }))
     }
-}
 
-impl Wasmi {
     fn can_run(&self, id: TestId) -> bool {
         match id {
             TestId::Execute(_) => {
@@ -110,6 +155,9 @@ impl RuntimeInstance for WasmiInstance {
     fn instantiate(&self, wasm: &[u8]) -> Box<dyn ModuleInstance> {
         let engine = self.linker.engine().clone();
         let mut store = <wasmi::Store<()>>::new(&engine, ());
+        if self.fuel {
+            store.set_fuel(u64::MAX).unwrap();
+        }
         let module = make_module(self.validation, &engine, wasm);
         let instance = self
             .linker
@@ -120,14 +168,16 @@ impl RuntimeInstance for WasmiInstance {
             instance,
             params: Vec::new(),
             results: Vec::new(),
+            funcs: utils::ResolvedFuncs::new(),
         })
     }
 }
 
-fn make_engine(compilation_mode: CompilationMode) -> wasmi::Engine {
+fn make_engine(compilation_mode: CompilationMode, fuel: bool) -> wasmi::Engine {
     let mut config = wasmi::Config::default();
     config.wasm_tail_call(true);
     config.compilation_mode(compilation_mode);
+    config.consume_fuel(fuel);
     wasmi::Engine::new(&config)
 }
 
@@ -151,13 +201,43 @@ impl ModuleInstance for WasmiModule {
         let Some(func) = self.instance.get_func(&self.store, name) else {
             anyhow::bail!("failed to find function")
         };
-        assert_eq!(params.len(), func.ty(&self.store).params().len());
-        assert_eq!(results.len(), func.ty(&self.store).results().len());
-        self.prepare_params(params);
-        self.prepare_results(&func);
-        func.call(&mut self.store, &self.params[..], &mut self.results[..])?;
-        self.write_back_results(results);
-        Ok(())
+        self.call_untyped(func, params, results)
+    }
+
+    fn has_func(&mut self, name: &str) -> bool {
+        self.instance.get_func(&self.store, name).is_some()
+    }
+
+    fn get_func(&mut self, name: &str) -> anyhow::Result<utils::ExportedFunc> {
+        self.funcs.resolve(name, || {
+            let Some(func) = self.instance.get_func(&self.store, name) else {
+                anyhow::bail!("failed to find function")
+            };
+            Ok(func)
+        })
+    }
+
+    fn call_func(
+        &mut self,
+        func: &utils::ExportedFunc,
+        params: &[utils::Val],
+        results: &mut [utils::Val],
+    ) -> anyhow::Result<()> {
+        let func = *self.funcs.get(func)?;
+        self.call_untyped(func, params, results)
+    }
+
+    fn call_func_typed(
+        &mut self,
+        func: &utils::ExportedFunc,
+        call: &mut dyn Any,
+    ) -> anyhow::Result<bool> {
+        utils::dispatch_typed_call!(call, |params: Params| -> Results {
+            let typed = self
+                .funcs
+                .typed(func, |func| Ok(func.typed::<Params, Results>(&self.store)?))?;
+            typed.call(&mut self.store, params)?
+        })
     }
 
     fn read_memory(&mut self, name: &str, ptr: u32, buffer: &mut [u8]) -> anyhow::Result<()> {
@@ -178,6 +258,19 @@ impl ModuleInstance for WasmiModule {
 }
 
 impl WasmiModule {
+    fn call_untyped(
+        &mut self,
+        func: Func,
+        params: &[utils::Val],
+        results: &mut [utils::Val],
+    ) -> anyhow::Result<()> {
+        self.prepare_params(params);
+        self.prepare_results(&func);
+        func.call(&mut self.store, &self.params[..], &mut self.results[..])?;
+        self.write_back_results(results);
+        Ok(())
+    }
+
     fn prepare_params(&mut self, params: &[utils::Val]) {
         self.params.clear();
         self.params
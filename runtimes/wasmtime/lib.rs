@@ -4,8 +4,10 @@
 use anyhow::bail;
 use benchmark_utils as utils;
 use benchmark_utils::{
-    ExecuteTestId, ModuleInstance, Runtime, RuntimeInstance, StartupTestId, TestId,
+    ExecuteTestId, Metering, ModuleInstance, Runtime, RuntimeInstance, RuntimeKind, StartupTestId,
+    TestId,
 };
+use core::any::Any;
 use wasmtime::{Func, Val, ValType};
 
 #[derive(Debug, Copy, Clone)]
@@ -22,6 +24,8 @@ pub struct Wasmtime {
 /// A concrete Wasmtime runtime with its linker, produced by [`Wasmtime::setup`].
 struct WasmtimeInstance {
     linker: wasmtime::Linker<()>,
+    /// The metering enabled on the engine, if any, set up anew for every instance.
+    metering: Option<Metering>,
 }
 
 /// An instantiated Wasmtime module, produced by [`WasmtimeInstance::instantiate`].
@@ -30,6 +34,8 @@ struct WasmtimeModule {
     instance: wasmtime::Instance,
     params: Vec<Val>,
     results: Vec<Val>,
+    /// The functions resolved by [`ModuleInstance::get_func`].
+    funcs: utils::ResolvedFuncs<Func>,
 }
 
 impl Runtime for Wasmtime {
@@ -41,22 +47,56 @@ impl Runtime for Wasmtime {
         }
     }
 
+    fn label(&self) -> &'static str {
+        match self.strategy {
+            Strategy::Cranelift => "Wasmtime (Cranelift)",
+            Strategy::Winch => "Wasmtime (Winch)",
+            Strategy::Pulley => "Wasmtime (Pulley)",
+        }
+    }
+
+    fn kind(&self) -> RuntimeKind {
+        match self.strategy {
+            Strategy::Cranelift | Strategy::Winch => RuntimeKind::Jit,
+            // Pulley is Wasmtime's interpreter, not one of its JITs.
+            Strategy::Pulley => RuntimeKind::Interpreter,
+        }
+    }
+
     fn setup(&self, id: TestId) -> Option<Box<dyn RuntimeInstance>> {
-        if !self.can_run(id) {
-            return None;
+        self.setup_with(id, None)
+    }
+
+    fn supports_metering(&self, metering: Metering) -> bool {
+        match metering {
+            Metering::Fuel | Metering::Epoch => true,
         }
-        let linker = wasmtime::Linker::new(&make_engine(self.strategy));
-        Some(Box::new(WasmtimeInstance { linker }))
+    }
+
+    fn setup_metered(&self, id: TestId, metering: Metering) -> Option<Box<dyn RuntimeInstance>> {
+        self.setup_with(id, Some(metering))
     }
 }
 
 impl Wasmtime {
+    fn setup_with(
+        &self,
+        id: TestId,
+        metering: Option<Metering>,
+    ) -> Option<Box<dyn RuntimeInstance>> {
+        if !self.can_run(id) {
+            return None;
+        }
+        let linker = wasmtime::Linker::new(&make_engine(self.strategy, metering));
+        Some(Box::new(WasmtimeInstance { linker, metering }))
+    }
+
     fn can_run(&self, id: TestId) -> bool {
         match self.strategy {
             Strategy::Cranelift => true,
-            Strategy::Winch => match id {
+            Strategy::Winch => match id.execute_case() {
                 // Note: winch does not support the Wasm `tail-call` proposal.
-                TestId::Execute(ExecuteTestId::FibonacciTail) => false,
+                Some(ExecuteTestId::FibonacciTail) => false,
                 _ => {
                     // Note: winch only works on `x86_64` and `aarch64`.
                     cfg!(target_arch = "x86_64") || cfg!(target_arch = "aarch64")
@@ -104,9 +144,20 @@ impl RuntimeInstance for WasmtimeInstance {
             .unwrap();
     }
 
+    fn compiled_size(&self, wasm: &[u8]) -> Option<usize> {
+        let module = wasmtime::Module::new(self.linker.engine(), wasm).unwrap();
+        Some(module.serialize().unwrap().len())
+    }
+
     fn instantiate(&self, wasm: &[u8]) -> Box<dyn ModuleInstance> {
         let engine = self.linker.engine().clone();
         let mut store = <wasmtime::Store<()>>::new(&engine, ());
+        match self.metering {
+            Some(Metering::Fuel) => store.set_fuel(u64::MAX).unwrap(),
+            // The engine's epoch is never incremented, so any deadline beyond it is never reached.
+            Some(Metering::Epoch) => store.set_epoch_deadline(1),
+            None => {}
+        }
         let module = wasmtime::Module::new(&engine, wasm).unwrap();
         let instance = self.linker.instantiate(&mut store, &module).unwrap();
         Box::new(WasmtimeModule {
@@ -114,12 +165,15 @@ impl RuntimeInstance for WasmtimeInstance {
             instance,
             params: Vec::new(),
             results: Vec::new(),
+            funcs: utils::ResolvedFuncs::new(),
         })
     }
 }
 
-fn make_engine(strategy: Strategy) -> wasmtime::Engine {
+fn make_engine(strategy: Strategy, metering: Option<Metering>) -> wasmtime::Engine {
     let mut config = wasmtime::Config::default();
+    config.consume_fuel(matches!(metering, Some(Metering::Fuel)));
+    config.epoch_interruption(matches!(metering, Some(Metering::Epoch)));
     if matches!(strategy, Strategy::Cranelift) {
         config.wasm_tail_call(true);
     }
@@ -143,13 +197,43 @@ impl ModuleInstance for WasmtimeModule {
         let Some(func) = self.instance.get_func(&mut self.store, name) else {
             anyhow::bail!("failed to find function")
         };
-        assert_eq!(params.len(), func.ty(&self.store).params().len());
-        assert_eq!(results.len(), func.ty(&self.store).results().len());
-        self.prepare_params(params);
-        self.prepare_results(&func);
-        func.call(&mut self.store, &self.params[..], &mut self.results[..])?;
-        self.write_back_results(results)?;
-        Ok(())
+        self.call_untyped(func, params, results)
+    }
+
+    fn has_func(&mut self, name: &str) -> bool {
+        self.instance.get_func(&mut self.store, name).is_some()
+    }
+
+    fn get_func(&mut self, name: &str) -> anyhow::Result<utils::ExportedFunc> {
+        self.funcs.resolve(name, || {
+            let Some(func) = self.instance.get_func(&mut self.store, name) else {
+                anyhow::bail!("failed to find function")
+            };
+            Ok(func)
+        })
+    }
+
+    fn call_func(
+        &mut self,
+        func: &utils::ExportedFunc,
+        params: &[utils::Val],
+        results: &mut [utils::Val],
+    ) -> anyhow::Result<()> {
+        let func = *self.funcs.get(func)?;
+        self.call_untyped(func, params, results)
+    }
+
+    fn call_func_typed(
+        &mut self,
+        func: &utils::ExportedFunc,
+        call: &mut dyn Any,
+    ) -> anyhow::Result<bool> {
+        utils::dispatch_typed_call!(call, |params: Params| -> Results {
+            let typed = self
+                .funcs
+                .typed(func, |func| Ok(func.typed::<Params, Results>(&self.store)?))?;
+            typed.call(&mut self.store, params)?
+        })
     }
 
     fn read_memory(&mut self, name: &str, ptr: u32, buffer: &mut [u8]) -> anyhow::Result<()> {
@@ -170,6 +254,19 @@ impl ModuleInstance for WasmtimeModule {
 }
 
 impl WasmtimeModule {
+    fn call_untyped(
+        &mut self,
+        func: Func,
+        params: &[utils::Val],
+        results: &mut [utils::Val],
+    ) -> anyhow::Result<()> {
+        self.prepare_params(params);
+        self.prepare_results(&func);
+        func.call(&mut self.store, &self.params[..], &mut self.results[..])?;
+        self.write_back_results(results)?;
+        Ok(())
+    }
+
     fn prepare_params(&mut self, params: &[utils::Val]) {
         self.params.clear();
         self.params
@@ -2,7 +2,7 @@
 
 use anyhow::bail;
 use benchmark_utils as utils;
-use benchmark_utils::{ModuleInstance, Runtime, RuntimeInstance, TestId};
+use benchmark_utils::{ModuleInstance, Runtime, RuntimeInstance, RuntimeKind, TestId};
 use wasmz::{Engine, Instance, Linker, Module, Store, Val, ValKind};
 
 pub struct Wasmz;
@@ -20,6 +20,8 @@ struct WasmzModule {
     module: Module,
     #[allow(dead_code, reason = "kept alive for instance lifetime")]
     store: Store,
+    /// The names of the exported functions, since wasmz only calls exports by name.
+    exports: Vec<Box<str>>,
 }
 
 impl Runtime for Wasmz {
@@ -27,6 +29,14 @@ impl Runtime for Wasmz {
         "wasmz"
     }
 
+    fn label(&self) -> &'static str {
+        "Wasmz"
+    }
+
+    fn kind(&self) -> RuntimeKind {
+        RuntimeKind::Interpreter
+    }
+
     fn setup(&self, id: TestId) -> Option<Box<dyn RuntimeInstance>> {
         if !self.can_run(id) {
             return None;
@@ -86,6 +96,7 @@ impl RuntimeInstance for WasmzInstance {
             instance,
             module,
             store,
+            exports: utils::func_exports(wasm),
         })
     }
 }
@@ -112,6 +123,10 @@ impl ModuleInstance for WasmzModule {
         Ok(())
     }
 
+    fn has_func(&mut self, name: &str) -> bool {
+        self.exports.iter().any(|export| &**export == name)
+    }
+
     fn read_memory(&mut self, name: &str, ptr: u32, buffer: &mut [u8]) -> anyhow::Result<()> {
         let _ = name;
         let Some((mem, size)) = self.instance.memory() else {
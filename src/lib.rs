@@ -1,6 +1,11 @@
 #![crate_type = "dylib"]
 
-use benchmark_utils::Runtime;
+pub mod results;
+
+use benchmark_utils::{Metering, Runtime, RuntimeInstance, RuntimeKind, TestId};
+use std::collections::BTreeSet;
+use std::rc::Rc;
+use std::sync::Mutex;
 
 /// Returns the Wasm runtimes with a set of configurations to test.
 pub fn vms_under_test() -> Vec<Box<dyn Runtime>> {
@@ -74,6 +79,82 @@ pub fn vms_under_test() -> Vec<Box<dyn Runtime>> {
     rts.into_vec()
 }
 
+/// Returns the Wasm runtimes of [`vms_under_test`] with every metering they support.
+///
+/// Their IDs append the metering to the ID of the unmetered runtime, e.g. `wasmtime.cranelift.fuel`.
+pub fn metered_vms_under_test() -> Vec<Box<dyn Runtime>> {
+    let mut rts = Vec::new();
+    for vm in vms_under_test() {
+        let vm: Rc<dyn Runtime> = Rc::from(vm);
+        for metering in [Metering::Fuel, Metering::Epoch] {
+            if vm.supports_metering(metering) {
+                rts.push(Box::new(Metered::new(vm.clone(), metering)) as Box<dyn Runtime>);
+            }
+        }
+    }
+    rts
+}
+
+/// A Wasm runtime of [`vms_under_test`] with its `metering` enabled.
+struct Metered {
+    vm: Rc<dyn Runtime>,
+    metering: Metering,
+    id: &'static str,
+    label: &'static str,
+}
+
+impl Metered {
+    /// Creates the metered `vm`.
+    ///
+    /// Interns its ID and label since [`Runtime`] hands them out as `&'static str`.
+    fn new(vm: Rc<dyn Runtime>, metering: Metering) -> Self {
+        let id = intern(format!("{}.{metering}", vm.id()));
+        let label = intern(format!("{} with {metering}", vm.label()));
+        Self {
+            vm,
+            metering,
+            id,
+            label,
+        }
+    }
+}
+
+/// Returns `string` as a `&'static str`.
+///
+/// Leaks every distinct `string` only once, no matter how often the runtimes are created.
+fn intern(string: String) -> &'static str {
+    static INTERNED: Mutex<BTreeSet<&'static str>> = Mutex::new(BTreeSet::new());
+    let mut interned = INTERNED.lock().unwrap_or_else(|error| error.into_inner());
+    if let Some(&interned) = interned.get(string.as_str()) {
+        return interned;
+    }
+    let leaked: &'static str = string.leak();
+    interned.insert(leaked);
+    leaked
+}
+
+impl Runtime for Metered {
+    fn id(&self) -> &'static str {
+        self.id
+    }
+
+    fn label(&self) -> &'static str {
+        self.label
+    }
+
+    fn kind(&self) -> RuntimeKind {
+        self.vm.kind()
+    }
+
+    fn is_outdated(&self) -> bool {
+        self.vm.is_outdated()
+    }
+
+    fn setup(&self, id: TestId) -> Option<Box<dyn RuntimeInstance>> {
+        self.vm.setup_metered(id, self.metering)
+    }
+}
+
 #[derive(Default)]
 struct Rts(Vec<Box<dyn Runtime>>);
 
@@ -0,0 +1,504 @@
+//! Decoding of the benchmark results emitted by `cargo criterion --message-format=json`.
+
+use benchmark_utils::RuntimeKind;
+use serde_json as json;
+use std::error::Error;
+use std::fmt::{self, Display};
+
+/// The identifier of a single benchmark: `{category}/{case}/{runtime}[/{input}]`.
+///
+/// For example `execute/fibonacci-rec/wasmi-v2.eager.checked/30`.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct BenchId {
+    /// The benchmark category, e.g. `execute` or `startup`.
+    pub category: String,
+    /// The test case name, e.g. `fibonacci-rec`.
+    pub case: String,
+    /// The Wasm runtime and its configuration, e.g. `wasmi-v2.eager.checked`.
+    pub runtime: String,
+    /// The optional input of the test case, e.g. `30`.
+    pub input: Option<String>,
+}
+
+impl BenchId {
+    /// Returns the name of the benchmark group of `self`: `{category}/{case}`.
+    pub fn group(&self) -> String {
+        format!("{}/{}", self.category, self.case)
+    }
+}
+
+impl std::str::FromStr for BenchId {
+    type Err = DecodeError;
+
+    fn from_str(id: &str) -> Result<Self, Self::Err> {
+        let mut parts = id.splitn(4, '/');
+        let (Some(category), Some(case), Some(runtime)) =
+            (parts.next(), parts.next(), parts.next())
+        else {
+            return Err(DecodeError::from(format!("malformed benchmark id: {id:?}")));
+        };
+        Ok(Self {
+            category: category.into(),
+            case: case.into(),
+            runtime: runtime.into(),
+            input: parts.next().map(String::from),
+        })
+    }
+}
+
+impl Display for BenchId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}/{}", self.category, self.case, self.runtime)?;
+        if let Some(input) = &self.input {
+            write!(f, "/{input}")?;
+        }
+        Ok(())
+    }
+}
+
+/// A point estimate and its confidence interval, normalized to nanoseconds.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Estimate {
+    /// The point estimate.
+    pub estimate: f64,
+    /// The lower bound of the confidence interval.
+    pub lower_bound: f64,
+    /// The upper bound of the confidence interval.
+    pub upper_bound: f64,
+}
+
+impl Estimate {
+    /// Decodes an [`Estimate`] from a criterion `{ estimate, lower_bound, upper_bound, unit }` object.
+    fn decode(value: &json::Value) -> Result<Self, DecodeError> {
+        let field = |name: &str| {
+            value
+                .get(name)
+                .and_then(json::Value::as_f64)
+                .ok_or_else(|| DecodeError::from(format!("malformed estimate `{name}`: {value}")))
+        };
+        let Some(unit) = value.get("unit").and_then(json::Value::as_str) else {
+            return Err(DecodeError::from(format!(
+                "malformed estimate `unit`: {value}"
+            )));
+        };
+        let estimate = field("estimate")?;
+        // Older `cargo criterion` versions omit the bounds: collapse them onto the estimate.
+        let lower_bound = field("lower_bound").unwrap_or(estimate);
+        let upper_bound = field("upper_bound").unwrap_or(estimate);
+        Ok(Self {
+            estimate: estimate_to_ns(estimate, unit)?,
+            lower_bound: estimate_to_ns(lower_bound, unit)?,
+            upper_bound: estimate_to_ns(upper_bound, unit)?,
+        })
+    }
+}
+
+/// The unit of a [`Throughput`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ThroughputUnit {
+    /// Bytes processed per iteration, e.g. of a Wasm module, rendered with binary prefixes.
+    Bytes,
+    /// Bytes processed per iteration, e.g. of an input text, rendered with decimal prefixes.
+    BytesDecimal,
+    /// Elements processed per iteration, e.g. sorted integers or loop iterations.
+    Elements,
+    /// Bits processed per iteration.
+    Bits,
+}
+
+/// The amount of work done by a single iteration of a benchmark.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Throughput {
+    /// The amount of work per iteration in [`Throughput::unit`].
+    pub per_iteration: u64,
+    /// The unit of the work.
+    pub unit: ThroughputUnit,
+}
+
+impl Throughput {
+    /// Decodes the first entry of a criterion `throughput` array, if any.
+    ///
+    /// Returns `Ok(None)` for an empty array as emitted by benchmarks without throughput.
+    fn decode(value: &json::Value) -> Result<Option<Self>, DecodeError> {
+        let Some(throughput) = value.as_array().and_then(|array| array.first()) else {
+            return Ok(None);
+        };
+        let Some(per_iteration) = throughput
+            .get("per_iteration")
+            .and_then(json::Value::as_u64)
+        else {
+            return Err(DecodeError::from(format!(
+                "malformed throughput `per_iteration`: {throughput}"
+            )));
+        };
+        let unit = match throughput.get("unit").and_then(json::Value::as_str) {
+            Some("bytes") => ThroughputUnit::Bytes,
+            Some("bytes_decimal") => ThroughputUnit::BytesDecimal,
+            Some("elements") => ThroughputUnit::Elements,
+            Some("bits") => ThroughputUnit::Bits,
+            _ => {
+                return Err(DecodeError::from(format!(
+                    "malformed throughput `unit`: {throughput}"
+                )));
+            }
+        };
+        Ok(Some(Self {
+            per_iteration,
+            unit,
+        }))
+    }
+
+    /// Formats the rate at which the work is done in `ns` nanoseconds per iteration.
+    ///
+    /// For example `12.34 MiB/s` for bytes, `123.45 MB/s` for decimal bytes or `1.20 Gops/s`
+    /// for elements.
+    pub fn format_rate(&self, ns: f64) -> String {
+        let per_second = self.per_iteration as f64 / (ns / 1_000_000_000.0);
+        let (unit, base, prefixes) = match self.unit {
+            ThroughputUnit::Bytes => ("B/s", 1024.0, ["", "Ki", "Mi", "Gi"]),
+            ThroughputUnit::BytesDecimal => ("B/s", 1000.0, ["", "k", "M", "G"]),
+            ThroughputUnit::Elements => ("ops/s", 1000.0, ["", "k", "M", "G"]),
+            ThroughputUnit::Bits => ("bit/s", 1000.0, ["", "k", "M", "G"]),
+        };
+        let mut value = per_second;
+        let mut prefix = prefixes[0];
+        for next in &prefixes[1..] {
+            if value < base {
+                break;
+            }
+            value /= base;
+            prefix = next;
+        }
+        format!("{value:.02} {prefix}{unit}")
+    }
+}
+
+/// The result of a single benchmark, decoded from a `benchmark-complete` message.
+#[derive(Debug, Clone)]
+pub struct Measurement {
+    /// The identifier of the benchmark.
+    pub id: BenchId,
+    /// The typical time of a single iteration.
+    pub typical: Estimate,
+    /// The work done by a single iteration, if the benchmark declares it.
+    pub throughput: Option<Throughput>,
+}
+
+impl Measurement {
+    /// Decodes a single line of `cargo criterion --message-format=json` output.
+    ///
+    /// Returns `Ok(None)` for messages other than `benchmark-complete`.
+    pub fn decode(line: &str) -> Result<Option<Self>, DecodeError> {
+        match Message::decode(line)? {
+            Some(Message::Benchmark(measurement)) => Ok(Some(measurement)),
+            _ => Ok(None),
+        }
+    }
+
+    /// Decodes the fields of a `benchmark-complete` message.
+    fn from_fields(fields: &Fields) -> Result<Self, DecodeError> {
+        let throughput = match fields.get("throughput") {
+            Some(throughput) => Throughput::decode(throughput)?,
+            None => None,
+        };
+        Ok(Self {
+            id: fields.id()?,
+            typical: Estimate::decode(fields.require("typical")?)?,
+            throughput,
+        })
+    }
+}
+
+/// The sizes of the Wasm module of a startup test case, decoded from a `module-info` message.
+///
+/// Only written by the benchmark harness itself, see `WASMI_BENCHMARKS_JSON`.
+#[derive(Debug, Clone)]
+pub struct ModuleInfo {
+    /// The benchmark group of the test case, e.g. `startup/bz2`.
+    pub group: String,
+    /// The size of the entire `.wasm` binary in bytes.
+    pub module_size: u64,
+    /// The size of the code section payload in bytes.
+    pub code_size: u64,
+    /// The number of functions defined by the module.
+    pub funcs: u64,
+}
+
+impl ModuleInfo {
+    /// Decodes the fields of a `module-info` message.
+    fn from_fields(fields: &Fields) -> Result<Self, DecodeError> {
+        Ok(Self {
+            group: fields.str("group")?.to_string(),
+            module_size: fields.u64("module_size")?,
+            code_size: fields.u64("code_size")?,
+            funcs: fields.u64("funcs")?,
+        })
+    }
+}
+
+/// The size of the artifact a runtime compiles from the Wasm module of a startup test case,
+/// decoded from a `compiled-size` message.
+///
+/// Only written by the benchmark harness itself for runtimes that expose the size.
+#[derive(Debug, Clone)]
+pub struct CompiledSize {
+    /// The identifier of the startup benchmark, e.g. `startup/bz2/wasmtime.cranelift`.
+    pub id: BenchId,
+    /// The size of the compiled artifact in bytes.
+    pub compiled_size: u64,
+}
+
+impl CompiledSize {
+    /// Decodes the fields of a `compiled-size` message.
+    fn from_fields(fields: &Fields) -> Result<Self, DecodeError> {
+        Ok(Self {
+            id: fields.id()?,
+            compiled_size: fields.u64("compiled_size")?,
+        })
+    }
+}
+
+/// The peak memory usage of a benchmark, decoded from a `memory-complete` message.
+///
+/// Only written by the benchmark harness itself, see `WASMI_BENCHMARKS_JSON`.
+#[derive(Debug, Clone)]
+pub struct MemoryUsage {
+    /// The identifier of the benchmark during which the memory usage was measured.
+    pub id: BenchId,
+    /// The peak number of heap bytes allocated through the global allocator.
+    pub heap_peak: u64,
+    /// The peak growth of the resident set size in bytes, if measured.
+    pub rss_peak: Option<u64>,
+}
+
+impl MemoryUsage {
+    /// Decodes the fields of a `memory-complete` message.
+    fn from_fields(fields: &Fields) -> Result<Self, DecodeError> {
+        let rss_peak = match fields.get("rss_peak") {
+            None | Some(json::Value::Null) => None,
+            Some(_) => Some(fields.u64("rss_peak")?),
+        };
+        Ok(Self {
+            id: fields.id()?,
+            heap_peak: fields.u64("heap_peak")?,
+            rss_peak,
+        })
+    }
+}
+
+/// The hardware performance counters of an execute benchmark per iteration, decoded from a
+/// `perf-complete` message.
+///
+/// Only written by the benchmark harness itself if `WASMI_BENCHMARKS_PERF` is set on Linux.
+#[derive(Debug, Clone)]
+pub struct PerfCounts {
+    /// The identifier of the benchmark during which the counters were read.
+    pub id: BenchId,
+    /// The number of retired instructions per iteration.
+    pub instructions: f64,
+    /// The number of CPU cycles per iteration.
+    pub cycles: f64,
+    /// The number of mispredicted branches per iteration.
+    pub branch_misses: f64,
+    /// The number of last level cache misses per iteration.
+    pub cache_misses: f64,
+}
+
+impl PerfCounts {
+    /// Decodes the fields of a `perf-complete` message.
+    fn from_fields(fields: &Fields) -> Result<Self, DecodeError> {
+        Ok(Self {
+            id: fields.id()?,
+            instructions: fields.f64("instructions")?,
+            cycles: fields.f64("cycles")?,
+            branch_misses: fields.f64("branch_misses")?,
+            cache_misses: fields.f64("cache_misses")?,
+        })
+    }
+}
+
+/// The metadata of a benchmarked Wasm runtime, decoded from a `runtime-info` message.
+///
+/// Only written by the benchmark harness itself, for every runtime in the order benchmarked.
+#[derive(Debug, Clone)]
+pub struct RuntimeInfo {
+    /// The unique ID, e.g. `wasmi-v2.eager.checked`.
+    pub id: String,
+    /// The human readable name, e.g. `Wasmi v2 (eager)`.
+    pub label: String,
+    /// The execution kind.
+    pub kind: RuntimeKind,
+    /// Whether a newer version of the Wasm runtime is benchmarked as well.
+    pub is_outdated: bool,
+}
+
+impl RuntimeInfo {
+    /// Decodes the fields of a `runtime-info` message.
+    fn from_fields(fields: &Fields) -> Result<Self, DecodeError> {
+        let kind = fields.decode("kind", |kind| match kind.as_str()? {
+            "jit" => Some(RuntimeKind::Jit),
+            "interpreter" => Some(RuntimeKind::Interpreter),
+            _ => None,
+        })?;
+        Ok(Self {
+            id: fields.str("id")?.to_string(),
+            label: fields.str("label")?.to_string(),
+            kind,
+            is_outdated: fields.decode("is_outdated", json::Value::as_bool)?,
+        })
+    }
+}
+
+/// A message of the benchmark results carrying data, decoded by its `reason`.
+#[derive(Debug, Clone)]
+pub enum Message {
+    /// A `benchmark-complete` message.
+    Benchmark(Measurement),
+    /// A `module-info` message.
+    ModuleInfo(ModuleInfo),
+    /// A `compiled-size` message.
+    CompiledSize(CompiledSize),
+    /// A `memory-complete` message.
+    MemoryUsage(MemoryUsage),
+    /// A `perf-complete` message.
+    PerfCounts(PerfCounts),
+    /// A `runtime-info` message.
+    RuntimeInfo(RuntimeInfo),
+}
+
+impl Message {
+    /// Decodes a single line of benchmark results, parsing it only once.
+    ///
+    /// Returns `Ok(None)` for all other messages, e.g. `group-complete`, `machine-info` or
+    /// the build messages of Cargo.
+    pub fn decode(line: &str) -> Result<Option<Self>, DecodeError> {
+        let message: json::Value = json::from_str(line)
+            .map_err(|error| DecodeError::from(format!("malformed JSON: {error}")))?;
+        let fields = Fields(&message);
+        let decoded = match message.get("reason").and_then(json::Value::as_str) {
+            Some("benchmark-complete") => Self::Benchmark(Measurement::from_fields(&fields)?),
+            Some("module-info") => Self::ModuleInfo(ModuleInfo::from_fields(&fields)?),
+            Some("compiled-size") => Self::CompiledSize(CompiledSize::from_fields(&fields)?),
+            Some("memory-complete") => Self::MemoryUsage(MemoryUsage::from_fields(&fields)?),
+            Some("perf-complete") => Self::PerfCounts(PerfCounts::from_fields(&fields)?),
+            Some("runtime-info") => Self::RuntimeInfo(RuntimeInfo::from_fields(&fields)?),
+            _ => return Ok(None),
+        };
+        Ok(Some(decoded))
+    }
+}
+
+/// The fields of a message, reporting missing or malformed ones with the entire message.
+struct Fields<'a>(&'a json::Value);
+
+impl Fields<'_> {
+    /// Returns the field `name`, if any.
+    fn get(&self, name: &str) -> Option<&json::Value> {
+        self.0.get(name)
+    }
+
+    /// Returns the field `name`.
+    fn require(&self, name: &str) -> Result<&json::Value, DecodeError> {
+        self.get(name)
+            .ok_or_else(|| DecodeError::from(format!("missing `{name}` value: {}", self.0)))
+    }
+
+    /// Returns the field `name` decoded by `decode`.
+    fn decode<'a, T>(
+        &'a self,
+        name: &str,
+        decode: impl FnOnce(&'a json::Value) -> Option<T>,
+    ) -> Result<T, DecodeError> {
+        self.get(name)
+            .and_then(decode)
+            .ok_or_else(|| DecodeError::from(format!("malformed `{name}` value: {}", self.0)))
+    }
+
+    /// Returns the string field `name`.
+    fn str(&self, name: &str) -> Result<&str, DecodeError> {
+        self.decode(name, json::Value::as_str)
+    }
+
+    /// Returns the unsigned integer field `name`.
+    fn u64(&self, name: &str) -> Result<u64, DecodeError> {
+        self.decode(name, json::Value::as_u64)
+    }
+
+    /// Returns the number field `name`.
+    fn f64(&self, name: &str) -> Result<f64, DecodeError> {
+        self.decode(name, json::Value::as_f64)
+    }
+
+    /// Returns the benchmark identifier in the `id` field.
+    fn id(&self) -> Result<BenchId, DecodeError> {
+        self.str("id")?.parse()
+    }
+}
+
+/// An error while decoding benchmark results.
+#[derive(Debug)]
+pub struct DecodeError {
+    message: String,
+}
+
+impl Error for DecodeError {}
+
+impl<S> From<S> for DecodeError
+where
+    S: Into<String>,
+{
+    fn from(message: S) -> Self {
+        Self {
+            message: message.into(),
+        }
+    }
+}
+
+impl Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.message.fmt(f)
+    }
+}
+
+/// Converts a `estimate` given in `unit` to nanoseconds.
+///
+/// Criterion reports times in one of `ns`, `us`/`µs`, `ms` or `s`; anything
+/// else is unexpected and treated as an error.
+pub fn estimate_to_ns(estimate: f64, unit: &str) -> Result<f64, DecodeError> {
+    let factor = match unit {
+        "ns" => 1.0,
+        "us" | "µs" => 1_000.0,
+        "ms" => 1_000_000.0,
+        "s" => 1_000_000_000.0,
+        _ => return Err(DecodeError::from(format!("unexpected time unit: {unit}"))),
+    };
+    Ok(estimate * factor)
+}
+
+/// Formats a nanosecond duration adaptively as `ns`, `µs`, `ms` or `s`.
+pub fn format_duration_ns(ns: f64) -> String {
+    let (value, unit) = if ns < 1_000.0 {
+        (ns, "ns")
+    } else if ns < 1_000_000.0 {
+        (ns / 1_000.0, "µs")
+    } else if ns < 1_000_000_000.0 {
+        (ns / 1_000_000.0, "ms")
+    } else {
+        (ns / 1_000_000_000.0, "s")
+    };
+    format!("{value:.02} {unit}")
+}
+
+/// Formats a number of bytes adaptively with binary prefixes as `B`, `KiB`, `MiB` or `GiB`.
+pub fn format_bytes(bytes: f64) -> String {
+    let (value, unit) = if bytes < 1024.0 {
+        (bytes, "B")
+    } else if bytes < 1024.0 * 1024.0 {
+        (bytes / 1024.0, "KiB")
+    } else if bytes < 1024.0 * 1024.0 * 1024.0 {
+        (bytes / (1024.0 * 1024.0), "MiB")
+    } else {
+        (bytes / (1024.0 * 1024.0 * 1024.0), "GiB")
+    };
+    format!("{value:.02} {unit}")
+}
@@ -4,6 +4,23 @@ use core::fmt;
 pub enum TestId {
     Startup(StartupTestId),
     Execute(ExecuteTestId),
+    /// Instantiates an execute test case and calls its `run` function once.
+    ///
+    /// Unlike [`TestId::Execute`] this is run on lazy compilation modes as well.
+    FirstCall(ExecuteTestId),
+}
+
+impl TestId {
+    /// Returns the execute test case of `self`, if any.
+    ///
+    /// Both [`TestId::Execute`] and [`TestId::FirstCall`] run an execute test case and thus
+    /// require the same Wasm proposals from a runtime.
+    pub fn execute_case(self) -> Option<ExecuteTestId> {
+        match self {
+            Self::Execute(id) | Self::FirstCall(id) => Some(id),
+            Self::Startup(_) => None,
+        }
+    }
 }
 
 impl From<StartupTestId> for TestId {
@@ -22,7 +39,7 @@ impl fmt::Display for TestId {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Self::Startup(id) => id.fmt(f),
-            Self::Execute(id) => id.fmt(f),
+            Self::Execute(id) | Self::FirstCall(id) => id.fmt(f),
         }
     }
 }
@@ -36,6 +53,8 @@ pub enum StartupTestId {
     Ffmpeg,
     CoreMark,
     Argon2,
+    /// A module with a single trivial export calling a single import, used to measure cold starts.
+    Trivial,
 }
 
 impl fmt::Display for StartupTestId {
@@ -48,6 +67,7 @@ impl fmt::Display for StartupTestId {
             Self::Ffmpeg => "ffmpeg",
             Self::CoreMark => "coremark",
             Self::Argon2 => "argon2",
+            Self::Trivial => "trivial",
         };
         f.write_str(s)
     }
@@ -78,6 +98,27 @@ pub enum ExecuteTestId {
     JsonParse,
     ReverseComplement,
     RegexRedux,
+    /// A Wasm loop calling an imported host function without parameters and results.
+    HostCallNullary,
+    /// A Wasm loop calling an imported host function with four `i64` parameters.
+    HostCallI64x4,
+    /// A Wasm loop calling an imported host function with mixed `f32` and `f64` parameters.
+    HostCallFloats,
+    /// A Wasm loop calling an imported host function with multiple results.
+    HostCallMultiValue,
+    /// A host loop calling a tiny exported Wasm function via [`ModuleInstance::call`].
+    ///
+    /// [`ModuleInstance::call`]: crate::ModuleInstance::call
+    HostCallExport,
+    /// A host loop calling the export of [`ExecuteTestId::HostCallExport`] via [`CallTyped`].
+    ///
+    /// [`CallTyped`]: crate::CallTyped
+    HostCallExportTyped,
+    /// A host loop calling the export of [`ExecuteTestId::HostCallExport`] via a [`TypedFunc`]
+    /// resolved once up front.
+    ///
+    /// [`TypedFunc`]: crate::TypedFunc
+    HostCallExportResolved,
 }
 
 impl fmt::Display for ExecuteTestId {
@@ -106,6 +147,13 @@ impl fmt::Display for ExecuteTestId {
             Self::JsonParse => "json-parse",
             Self::ReverseComplement => "reverse-complement",
             Self::RegexRedux => "regex-redux",
+            Self::HostCallNullary => "host-call-nullary",
+            Self::HostCallI64x4 => "host-call-i64x4",
+            Self::HostCallFloats => "host-call-floats",
+            Self::HostCallMultiValue => "host-call-multi-value",
+            Self::HostCallExport => "host-call-export",
+            Self::HostCallExportTyped => "host-call-export-typed",
+            Self::HostCallExportResolved => "host-call-export-resolved",
         };
         f.write_str(s)
     }
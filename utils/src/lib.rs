@@ -2,16 +2,48 @@
 
 mod id;
 mod linker;
+mod module_stats;
+mod resolved_funcs;
 mod typed_call;
 mod val;
 
 pub use self::id::{ExecuteTestId, StartupTestId, TestId};
 pub use self::linker::{HostFunc, Linker};
-pub use self::typed_call::{CallTyped, WasmParams, WasmResults, WasmValue};
+pub use self::module_stats::{ModuleStats, func_exports};
+pub use self::resolved_funcs::ResolvedFuncs;
+pub use self::typed_call::{CallTyped, TypedCall, TypedFunc, WasmParams, WasmResults, WasmValue};
 pub use self::val::{FuncType, TypeMismatch, Val, ValType};
+use core::any::Any;
 use core::fmt;
 use std::fs;
 
+/// The execution kind of a Wasm runtime.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum RuntimeKind {
+    /// A JIT-compiling Wasm runtime.
+    Jit,
+    /// An interpreter-based Wasm runtime.
+    Interpreter,
+}
+
+/// A way of bounding the execution of untrusted Wasm code.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Metering {
+    /// Consumes fuel for executed instructions and traps once it runs out.
+    Fuel,
+    /// Checks a deadline at function entries and loop headers, e.g. for timeouts.
+    Epoch,
+}
+
+impl fmt::Display for Metering {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Fuel => "fuel".fmt(f),
+            Self::Epoch => "epoch".fmt(f),
+        }
+    }
+}
+
 /// A WebAssembly runtime description.
 ///
 /// Represents a Wasm runtime with a specific configuration.
@@ -19,10 +51,42 @@ pub trait Runtime {
     /// Returns the unique ID of the Wasm runtime and its configuration as string.
     fn id(&self) -> &'static str;
 
+    /// Returns the human readable name of the Wasm runtime and its configuration.
+    ///
+    /// For example `Wasmi v2 (eager)` for `wasmi-v2.eager.checked`.
+    fn label(&self) -> &'static str;
+
+    /// Returns the execution kind of the Wasm runtime with its configuration.
+    fn kind(&self) -> RuntimeKind;
+
+    /// Returns `true` if a newer version of this Wasm runtime is benchmarked as well.
+    ///
+    /// Configurations of the same version are never considered newer than one
+    /// another, only entire runtime versions are.
+    fn is_outdated(&self) -> bool {
+        false
+    }
+
     /// Sets up and returns a [`RuntimeInstance`] if `self` can run `id`.
     ///
     /// Otherwise returns `None`.
     fn setup(&self, id: TestId) -> Option<Box<dyn RuntimeInstance>>;
+
+    /// Returns `true` if `self` can bound the execution of Wasm code via `metering`.
+    ///
+    /// Returns `false` by default for runtimes without any metering.
+    fn supports_metering(&self, _metering: Metering) -> bool {
+        false
+    }
+
+    /// Sets up and returns a [`RuntimeInstance`] with `metering` enabled if `self` can run `id`.
+    ///
+    /// The metering never interrupts execution: all fuel or time is available to every
+    /// instance, so that only its overhead is measured. Returns `None` if `self` does not
+    /// support `metering`, see [`Runtime::supports_metering`].
+    fn setup_metered(&self, _id: TestId, _metering: Metering) -> Option<Box<dyn RuntimeInstance>> {
+        None
+    }
 }
 
 /// A concrete instance of a WebAssembly (Wasm) runtime.
@@ -42,6 +106,14 @@ pub trait RuntimeInstance {
 
     /// Instantiates the `wasm` module with previously linked functions.
     fn instantiate(&self, wasm: &[u8]) -> Box<dyn ModuleInstance>;
+
+    /// Compiles the `wasm` module and returns the size of the compiled artifact in bytes.
+    ///
+    /// For example the size of the serialized machine code of a JIT. Returns `None` by
+    /// default for runtimes that do not expose the size of their compiled artifacts.
+    fn compiled_size(&self, _wasm: &[u8]) -> Option<usize> {
+        None
+    }
 }
 
 /// A module instance of a WebAssembly (Wasm) runtime.
@@ -53,6 +125,53 @@ pub trait ModuleInstance {
     /// It is the callers responsibility to provide `params` and `results` buffers big enough to satisfy the called function.
     fn call(&mut self, name: &str, params: &[Val], results: &mut [Val]) -> anyhow::Result<()>;
 
+    /// Returns `true` if `self` exports a function by `name`.
+    fn has_func(&mut self, name: &str) -> bool;
+
+    /// Resolves the function exported by `name` once for repeated calls via [`Self::call_func`].
+    ///
+    /// By default only checks that the export exists and records `name`, so that
+    /// [`Self::call_func`] still looks up the export on every call. Adapters resolve it up front
+    /// instead, usually via [`ResolvedFuncs`].
+    ///
+    /// # Errors
+    ///
+    /// If `self` does not export a function by `name`.
+    fn get_func(&mut self, name: &str) -> anyhow::Result<ExportedFunc> {
+        if !self.has_func(name) {
+            anyhow::bail!("missing function export: {name}")
+        }
+        Ok(ExportedFunc::new(name, 0))
+    }
+
+    /// Calls the `func` resolved by [`Self::get_func`] with `params` and writes the results back into `results`.
+    ///
+    /// # Note
+    ///
+    /// Just like [`Self::call`] it is the callers responsibility to provide big enough buffers.
+    fn call_func(
+        &mut self,
+        func: &ExportedFunc,
+        params: &[Val],
+        results: &mut [Val],
+    ) -> anyhow::Result<()> {
+        self.call(func.name(), params, results)
+    }
+
+    /// Calls the `func` resolved by [`Self::get_func`] with the [`TypedCall`] behind `call`.
+    ///
+    /// Returns `false` without calling `func` if the Wasm runtime has no native typed function
+    /// for the signature of `call`, so that [`TypedFunc`] falls back to [`Self::call_func`].
+    /// By default this is the case for all signatures. Adapters with a native typed function API
+    /// dispatch to it via [`dispatch_typed_call!`].
+    fn call_func_typed(
+        &mut self,
+        _func: &ExportedFunc,
+        _call: &mut dyn Any,
+    ) -> anyhow::Result<bool> {
+        Ok(false)
+    }
+
     /// Reads from the memory exported from `self` by `name`: `buffer = memory[ptr..ptr+buffer.len()]`
     fn read_memory(&mut self, name: &str, ptr: u32, buffer: &mut [u8]) -> anyhow::Result<()>;
 
@@ -60,6 +179,49 @@ pub trait ModuleInstance {
     fn write_memory(&mut self, name: &str, ptr: u32, buffer: &[u8]) -> anyhow::Result<()>;
 }
 
+/// A function exported by a [`ModuleInstance`], resolved via [`ModuleInstance::get_func`].
+#[derive(Debug, Clone)]
+pub struct ExportedFunc {
+    name: Box<str>,
+    index: usize,
+    /// The [`ResolvedFuncs`] that resolved the export, or 0 if none.
+    owner: usize,
+}
+
+impl ExportedFunc {
+    /// Creates a new [`ExportedFunc`] for the export `name`.
+    ///
+    /// The meaning of `index` is up to the [`ModuleInstance`] resolving the export, e.g. the
+    /// position of the resolved function within its table of resolved functions.
+    pub fn new(name: &str, index: usize) -> Self {
+        Self::owned(name, index, 0)
+    }
+
+    /// Creates a new [`ExportedFunc`] for the export `name` resolved by the [`ResolvedFuncs`] `owner`.
+    fn owned(name: &str, index: usize, owner: usize) -> Self {
+        Self {
+            name: name.into(),
+            index,
+            owner,
+        }
+    }
+
+    /// Returns the name of the export.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the index assigned by the [`ModuleInstance`] that resolved the export.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Returns the [`ResolvedFuncs`] that resolved the export, or 0 if none.
+    fn owner(&self) -> usize {
+        self.owner
+    }
+}
+
 /// Converts the `.wat` encoded `bytes` into `.wasm` encoded bytes.
 pub fn wat2wasm(bytes: &[u8]) -> Vec<u8> {
     wat::parse_bytes(bytes).unwrap().into_owned()
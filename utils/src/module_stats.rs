@@ -0,0 +1,113 @@
+/// The sizes of an encoded Wasm module.
+///
+/// Startup times mostly scale with the amount of code to validate and translate, so these
+/// normalize startup times of differently sized modules.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct ModuleStats {
+    /// The size of the entire `.wasm` binary in bytes.
+    pub module_size: usize,
+    /// The size of the code section payload in bytes, i.e. of all function bodies.
+    pub code_size: usize,
+    /// The number of functions defined by the module, i.e. with a body in the code section.
+    pub funcs: u32,
+}
+
+impl ModuleStats {
+    /// The ID of the code section.
+    const CODE_SECTION: u8 = 10;
+
+    /// Computes the [`ModuleStats`] of the `.wasm` binary `wasm`.
+    ///
+    /// # Panics
+    ///
+    /// If `wasm` is not a well-formed sequence of Wasm sections.
+    pub fn new(wasm: &[u8]) -> Self {
+        let mut stats = Self {
+            module_size: wasm.len(),
+            code_size: 0,
+            funcs: 0,
+        };
+        for (id, payload) in sections(wasm) {
+            if id == Self::CODE_SECTION {
+                stats.code_size = payload.len();
+                stats.funcs = read_u32(payload).0;
+            }
+        }
+        stats
+    }
+}
+
+/// Returns the names of all functions exported by the `.wasm` binary `wasm`.
+///
+/// For Wasm runtimes without a way to look up exports by name, see [`ModuleInstance::has_func`].
+///
+/// # Panics
+///
+/// If `wasm` is not a well-formed sequence of Wasm sections or has a malformed export section.
+///
+/// [`ModuleInstance::has_func`]: crate::ModuleInstance::has_func
+pub fn func_exports(wasm: &[u8]) -> Vec<Box<str>> {
+    /// The ID of the export section.
+    const EXPORT_SECTION: u8 = 7;
+    /// The kind of exported functions.
+    const FUNC_EXPORT: u8 = 0;
+    let mut names = Vec::new();
+    for (id, payload) in sections(wasm) {
+        if id != EXPORT_SECTION {
+            continue;
+        }
+        let (len, mut exports) = read_u32(payload);
+        for _ in 0..len {
+            let (name_len, rest) = read_u32(exports);
+            let Some((name, rest)) = rest.split_at_checked(name_len as usize) else {
+                panic!("malformed Wasm module: export name exceeds the export section")
+            };
+            let Some((&kind, rest)) = rest.split_first() else {
+                panic!("malformed Wasm module: missing export kind")
+            };
+            if kind == FUNC_EXPORT {
+                names.push(String::from_utf8_lossy(name).into());
+            }
+            // Skips the index of the exported item.
+            exports = read_u32(rest).1;
+        }
+    }
+    names
+}
+
+/// Returns the ID and payload of every section of the `.wasm` binary `wasm`.
+///
+/// # Panics
+///
+/// If `wasm` is not a well-formed sequence of Wasm sections.
+fn sections(wasm: &[u8]) -> impl Iterator<Item = (u8, &[u8])> {
+    // Skips the magic number and version of the binary format.
+    let mut sections = wasm
+        .get(8..)
+        .unwrap_or_else(|| panic!("malformed Wasm module: missing header"));
+    core::iter::from_fn(move || {
+        let (&id, rest) = sections.split_first()?;
+        let (size, rest) = read_u32(rest);
+        let Some((payload, rest)) = rest.split_at_checked(size as usize) else {
+            panic!("malformed Wasm module: section {id} exceeds the module")
+        };
+        sections = rest;
+        Some((id, payload))
+    })
+}
+
+/// Reads a LEB128 encoded `u32` from the start of `bytes` and returns it with the remaining bytes.
+///
+/// # Panics
+///
+/// If `bytes` does not start with a valid encoding.
+fn read_u32(bytes: &[u8]) -> (u32, &[u8]) {
+    let mut value = 0_u32;
+    for (index, &byte) in bytes.iter().enumerate().take(5) {
+        value |= u32::from(byte & 0x7F) << (7 * index);
+        if byte & 0x80 == 0 {
+            return (value, &bytes[index + 1..]);
+        }
+    }
+    panic!("malformed Wasm module: invalid LEB128 encoded `u32`")
+}
@@ -0,0 +1,127 @@
+use crate::ExportedFunc;
+use anyhow::bail;
+use core::any::Any;
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+/// The functions resolved by [`ModuleInstance::get_func`] of a [`ModuleInstance`].
+///
+/// Generic over the Wasm runtime's function type `F`, so that adapters only supply the lookup of
+/// the export and the construction of the runtime's native typed function.
+///
+/// [`ModuleInstance`]: crate::ModuleInstance
+/// [`ModuleInstance::get_func`]: crate::ModuleInstance::get_func
+#[derive(Debug)]
+pub struct ResolvedFuncs<F> {
+    /// Identifies the [`ExportedFunc`]s handed out by `self` amongst those of all other instances.
+    owner: usize,
+    /// The resolved functions, indexed by [`ExportedFunc::index`].
+    funcs: Vec<ResolvedFunc<F>>,
+}
+
+/// A function resolved by [`ResolvedFuncs::resolve`].
+#[derive(Debug)]
+struct ResolvedFunc<F> {
+    /// The name of the export.
+    name: Box<str>,
+    /// The resolved function of the Wasm runtime.
+    func: F,
+    /// The native typed function of the most recent [`ResolvedFuncs::typed`] call, if any.
+    typed: Option<Box<dyn Any>>,
+}
+
+impl<F> Default for ResolvedFuncs<F> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<F> ResolvedFuncs<F> {
+    /// Creates new [`ResolvedFuncs`] without any resolved functions.
+    pub fn new() -> Self {
+        /// The owner of the next [`ResolvedFuncs`], starting at 1 since 0 marks unowned handles.
+        static NEXT_OWNER: AtomicUsize = AtomicUsize::new(1);
+        Self {
+            owner: NEXT_OWNER.fetch_add(1, Ordering::Relaxed),
+            funcs: Vec::new(),
+        }
+    }
+
+    /// Resolves the function exported by `name` via `lookup` unless it is already resolved.
+    ///
+    /// Repeated calls with the same `name` return the same [`ExportedFunc`].
+    ///
+    /// # Errors
+    ///
+    /// If `lookup` fails, e.g. if there is no function exported by `name`.
+    pub fn resolve(
+        &mut self,
+        name: &str,
+        lookup: impl FnOnce() -> anyhow::Result<F>,
+    ) -> anyhow::Result<ExportedFunc> {
+        if let Some(index) = self.funcs.iter().position(|func| &*func.name == name) {
+            return Ok(ExportedFunc::owned(name, index, self.owner));
+        }
+        let index = self.funcs.len();
+        self.funcs.push(ResolvedFunc {
+            name: name.into(),
+            func: lookup()?,
+            typed: None,
+        });
+        Ok(ExportedFunc::owned(name, index, self.owner))
+    }
+
+    /// Returns the resolved function of `func`.
+    ///
+    /// # Errors
+    ///
+    /// If `func` was not resolved by `self`, e.g. by another instance.
+    pub fn get(&self, func: &ExportedFunc) -> anyhow::Result<&F> {
+        self.lookup(func).map(|index| &self.funcs[index].func)
+    }
+
+    /// Returns the native typed function `T` of `func`, created via `typed` on first use.
+    ///
+    /// Only the native typed function of the most recent call is kept, so that alternating the
+    /// signature of calls to the same `func` recreates it each time.
+    ///
+    /// # Errors
+    ///
+    /// - If `func` was not resolved by `self`, e.g. by another instance.
+    /// - If `typed` fails, e.g. if `T` does not match the signature of `func`.
+    pub fn typed<T>(
+        &mut self,
+        func: &ExportedFunc,
+        typed: impl FnOnce(&F) -> anyhow::Result<T>,
+    ) -> anyhow::Result<&T>
+    where
+        T: Any,
+    {
+        let index = self.lookup(func)?;
+        let resolved = &mut self.funcs[index];
+        if !resolved.typed.as_ref().is_some_and(|typed| typed.is::<T>()) {
+            resolved.typed = Some(Box::new(typed(&resolved.func)?));
+        }
+        let typed = resolved
+            .typed
+            .as_ref()
+            .and_then(|typed| typed.downcast_ref());
+        Ok(typed.expect("the typed function was just created"))
+    }
+
+    /// Returns the index of `func` within `self`.
+    ///
+    /// # Errors
+    ///
+    /// If `func` was not resolved by `self`, e.g. by another instance.
+    fn lookup(&self, func: &ExportedFunc) -> anyhow::Result<usize> {
+        let index = func.index();
+        if func.owner() != self.owner || index >= self.funcs.len() {
+            bail!(
+                "function {:?} was not resolved by this instance: index {index} of {}",
+                func.name(),
+                self.funcs.len(),
+            );
+        }
+        Ok(index)
+    }
+}
@@ -1,4 +1,6 @@
-use crate::{ModuleInstance, TypeMismatch, Val, ValType};
+use crate::{ExportedFunc, ModuleInstance, TypeMismatch, Val, ValType};
+use anyhow::bail;
+use core::marker::PhantomData;
 
 /// Extension to [`ModuleInstance`] to allow for simpler typed calls.
 pub trait CallTyped {
@@ -15,6 +17,19 @@ pub trait CallTyped {
     where
         Params: WasmParams,
         Results: WasmResults;
+
+    /// Resolves the function exported with `name` once into a [`TypedFunc`] for repeated calls.
+    ///
+    /// # Note
+    ///
+    /// This avoids looking up the export by `name` on every call as done by [`CallTyped::call_typed`].
+    fn typed_func<Params, Results>(
+        &mut self,
+        name: &str,
+    ) -> anyhow::Result<TypedFunc<Params, Results>>
+    where
+        Params: WasmParams,
+        Results: WasmResults;
 }
 
 impl<T> CallTyped for Box<T>
@@ -28,11 +43,141 @@ where
     {
         let params = Params::params(params);
         let mut results = Results::results();
-        self.call(name, params.as_ref(), results.as_mut()).unwrap();
-        Ok(Results::from_results(results))
+        self.call(name, params.as_ref(), results.as_mut())?;
+        Ok(Results::from_results(results)?)
+    }
+
+    fn typed_func<Params, Results>(
+        &mut self,
+        name: &str,
+    ) -> anyhow::Result<TypedFunc<Params, Results>>
+    where
+        Params: WasmParams,
+        Results: WasmResults,
+    {
+        let func = self.get_func(name)?;
+        Ok(TypedFunc {
+            func,
+            marker: PhantomData,
+        })
     }
 }
 
+/// An [`ExportedFunc`] with statically known parameter and result types.
+///
+/// Created by [`CallTyped::typed_func`].
+#[derive(Debug, Clone)]
+pub struct TypedFunc<Params, Results> {
+    func: ExportedFunc,
+    marker: PhantomData<fn(Params) -> Results>,
+}
+
+impl<Params, Results> TypedFunc<Params, Results>
+where
+    Params: WasmParams + 'static,
+    Results: WasmResults + 'static,
+{
+    /// Calls the function on the `instance` that resolved it with `params` and returns its results.
+    ///
+    /// # Note
+    ///
+    /// Saves looking up the export by name and, where the adapter supports the signature via
+    /// [`ModuleInstance::call_func_typed`], passes `params` and results natively typed to the
+    /// Wasm runtime. Otherwise they are passed via the dynamically typed
+    /// [`ModuleInstance::call_func`].
+    pub fn call(
+        &self,
+        instance: &mut (impl ModuleInstance + ?Sized),
+        params: Params,
+    ) -> anyhow::Result<Results> {
+        let mut call = TypedCall::<Params, Results> {
+            params: Some(params),
+            results: None,
+        };
+        if instance.call_func_typed(&self.func, &mut call)? {
+            let Some(results) = call.results else {
+                bail!("missing results of typed call to {:?}", self.func.name())
+            };
+            return Ok(results);
+        }
+        let Some(params) = call.params else {
+            bail!("missing parameters of typed call to {:?}", self.func.name())
+        };
+        let params = Params::params(params);
+        let mut results = Results::results();
+        instance.call_func(&self.func, params.as_ref(), results.as_mut())?;
+        Ok(Results::from_results(results)?)
+    }
+}
+
+/// The parameters and results of a call via [`ModuleInstance::call_func_typed`].
+#[derive(Debug)]
+pub struct TypedCall<Params, Results> {
+    /// The parameters of the call, taken by the adapter that performs it.
+    pub params: Option<Params>,
+    /// The results of the call, set by the adapter that performed it.
+    pub results: Option<Results>,
+}
+
+/// Implements [`ModuleInstance::call_func_typed`] via the native typed function API of a Wasm runtime.
+///
+/// Returns `Ok(true)` from the enclosing function after evaluating `$body` to the results of the
+/// call with the `$params` of the [`TypedCall`] behind `$call` and storing them, with `$Params`
+/// and `$Results` as type aliases of its parameters and results. Errors propagate via `?`.
+///
+/// Evaluates to `Ok(false)` for all other signatures, i.e. unless parameters and results each are
+/// either `()` or a single [`WasmValue`]. Runtimes whose typed function API lacks some of them
+/// list the supported ones, e.g. `dispatch_typed_call!(call, [i32 i64], |params: ...| ...)`.
+///
+/// # Example
+///
+/// ```ignore
+/// fn call_func_typed(&mut self, func: &ExportedFunc, call: &mut dyn Any) -> anyhow::Result<bool> {
+///     dispatch_typed_call!(call, |params: Params| -> Results {
+///         let typed = self.funcs.typed(func, |func| Ok(func.typed::<Params, Results>(&self.store)?))?;
+///         typed.call(&mut self.store, params)?
+///     })
+/// }
+/// ```
+#[macro_export]
+macro_rules! dispatch_typed_call {
+    ( $call:expr, |$params:ident: $Params:ident| -> $Results:ident $body:block ) => {
+        $crate::dispatch_typed_call!(
+            $call, [i32 i64 f32 f64], |$params: $Params| -> $Results $body
+        )
+    };
+    ( $call:expr, [$( $ty:tt )*], |$params:ident: $Params:ident| -> $Results:ident $body:block ) => {{
+        let call: &mut dyn ::core::any::Any = $call;
+        $crate::dispatch_typed_call!(
+            @params call, $params, $Params, $Results, $body, [$( $ty )* ()], [$( $ty )* ()]
+        );
+        ::core::result::Result::Ok(false)
+    }};
+    ( @params $call:ident, $params:ident, $Params:ident, $Results:ident, $body:block,
+      [$( $param:tt )*], $results:tt ) => {
+        $(
+            $crate::dispatch_typed_call!(
+                @results $call, $params, $Params, $Results, $body, $param, $results
+            );
+        )*
+    };
+    ( @results $call:ident, $params:ident, $Params:ident, $Results:ident, $body:block,
+      $param:tt, [$( $result:tt )*] ) => {
+        $(
+            if let ::core::option::Option::Some(call) =
+                $call.downcast_mut::<$crate::TypedCall<$param, $result>>()
+            {
+                type $Params = $param;
+                type $Results = $result;
+                if let ::core::option::Option::Some($params) = call.params.take() {
+                    call.results = ::core::option::Option::Some($body);
+                    return ::core::result::Result::Ok(true);
+                }
+            }
+        )*
+    };
+}
+
 /// Trait implemented by all primitive Wasm types.
 pub trait WasmValue:
     Send + Sync + Copy + Clone + PartialEq + PartialOrd + Into<Val> + TryFrom<Val, Error = TypeMismatch>
@@ -83,7 +228,7 @@ where
     }
 }
 
-pub trait WasmResults {
+pub trait WasmResults: Sized {
     /// The results buffer, an array of [`Val`] with known length.
     type ResultsBuffer: AsMut<[Val]>;
 
@@ -91,7 +236,11 @@ pub trait WasmResults {
     fn results() -> Self::ResultsBuffer;
 
     /// Creates `Self` from the updated results of the function call.
-    fn from_results(results: Self::ResultsBuffer) -> Self;
+    ///
+    /// # Errors
+    ///
+    /// If the types of the `results` do not match the types of `Self`.
+    fn from_results(results: Self::ResultsBuffer) -> Result<Self, TypeMismatch>;
 }
 
 impl WasmResults for () {
@@ -103,7 +252,9 @@ impl WasmResults for () {
     }
 
     #[inline]
-    fn from_results(_results: Self::ResultsBuffer) -> Self {}
+    fn from_results(_results: Self::ResultsBuffer) -> Result<Self, TypeMismatch> {
+        Ok(())
+    }
 }
 
 impl<T> WasmResults for T
@@ -118,8 +269,77 @@ where
     }
 
     #[inline]
-    fn from_results(results: Self::ResultsBuffer) -> Self {
+    fn from_results(results: Self::ResultsBuffer) -> Result<Self, TypeMismatch> {
         let [result] = results;
-        <Self as TryFrom<Val>>::try_from(result).unwrap()
+        <Self as TryFrom<Val>>::try_from(result)
     }
 }
+
+/// Implements [`WasmParams`] and [`WasmResults`] for tuples of [`WasmValue`]s.
+macro_rules! impl_wasm_params_results_for_tuples {
+    ( $( $len:literal => ( $( $ty:ident $val:ident ),* ) );* $(;)? ) => {
+        $(
+            impl<$($ty),*> WasmParams for ($($ty,)*)
+            where
+                $( $ty: WasmValue, )*
+            {
+                type ParamBuffer = [Val; $len];
+
+                #[inline]
+                fn params(self) -> Self::ParamBuffer {
+                    let ($($val,)*) = self;
+                    [$($val.into()),*]
+                }
+            }
+
+            impl<$($ty),*> WasmResults for ($($ty,)*)
+            where
+                $( $ty: WasmValue, )*
+            {
+                type ResultsBuffer = [Val; $len];
+
+                #[inline]
+                fn results() -> Self::ResultsBuffer {
+                    [$(Val::default_for_ty($ty::TY)),*]
+                }
+
+                #[inline]
+                fn from_results(results: Self::ResultsBuffer) -> Result<Self, TypeMismatch> {
+                    let [$($val),*] = results;
+                    Ok(($(<$ty as TryFrom<Val>>::try_from($val)?,)*))
+                }
+            }
+        )*
+    };
+}
+
+impl_wasm_params_results_for_tuples! {
+    1 => (T0 v0);
+    2 => (T0 v0, T1 v1);
+    3 => (T0 v0, T1 v1, T2 v2);
+    4 => (T0 v0, T1 v1, T2 v2, T3 v3);
+    5 => (T0 v0, T1 v1, T2 v2, T3 v3, T4 v4);
+    6 => (T0 v0, T1 v1, T2 v2, T3 v3, T4 v4, T5 v5);
+    7 => (T0 v0, T1 v1, T2 v2, T3 v3, T4 v4, T5 v5, T6 v6);
+    8 => (T0 v0, T1 v1, T2 v2, T3 v3, T4 v4, T5 v5, T6 v6, T7 v7);
+    9 => (T0 v0, T1 v1, T2 v2, T3 v3, T4 v4, T5 v5, T6 v6, T7 v7, T8 v8);
+    10 => (T0 v0, T1 v1, T2 v2, T3 v3, T4 v4, T5 v5, T6 v6, T7 v7, T8 v8, T9 v9);
+    11 => (T0 v0, T1 v1, T2 v2, T3 v3, T4 v4, T5 v5, T6 v6, T7 v7, T8 v8, T9 v9, T10 v10);
+    12 => (T0 v0, T1 v1, T2 v2, T3 v3, T4 v4, T5 v5, T6 v6, T7 v7, T8 v8, T9 v9, T10 v10, T11 v11);
+    13 => (
+        T0 v0, T1 v1, T2 v2, T3 v3, T4 v4, T5 v5, T6 v6,
+        T7 v7, T8 v8, T9 v9, T10 v10, T11 v11, T12 v12
+    );
+    14 => (
+        T0 v0, T1 v1, T2 v2, T3 v3, T4 v4, T5 v5, T6 v6,
+        T7 v7, T8 v8, T9 v9, T10 v10, T11 v11, T12 v12, T13 v13
+    );
+    15 => (
+        T0 v0, T1 v1, T2 v2, T3 v3, T4 v4, T5 v5, T6 v6, T7 v7,
+        T8 v8, T9 v9, T10 v10, T11 v11, T12 v12, T13 v13, T14 v14
+    );
+    16 => (
+        T0 v0, T1 v1, T2 v2, T3 v3, T4 v4, T5 v5, T6 v6, T7 v7,
+        T8 v8, T9 v9, T10 v10, T11 v11, T12 v12, T13 v13, T14 v14, T15 v15
+    );
+}
@@ -111,6 +111,8 @@ impl fmt::Display for TypeMismatch {
     }
 }
 
+impl std::error::Error for TypeMismatch {}
+
 macro_rules! impl_val {
     ( $( $camel:ident($snake:ident) = { fn $unwrap:ident }),* $(,)? ) => {
         $(